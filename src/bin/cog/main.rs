@@ -2,9 +2,10 @@ mod commit;
 
 use std::path::PathBuf;
 
+use cocogitto::command::bump::BumpOptions;
 use cocogitto::conventional::changelog::template::{RemoteContext, Template};
 use cocogitto::conventional::commit as conv_commit;
-use cocogitto::conventional::version::IncrementCommand;
+use cocogitto::conventional::version::{Increment, IncrementCommand};
 use cocogitto::git::hook::HookKind;
 use cocogitto::git::revspec::RevspecPattern;
 use cocogitto::log::filter::{CommitFilter, CommitFilters};
@@ -99,6 +100,12 @@ impl ValueEnum for Shell {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
 /// A command line tool for the conventional commits and semver specifications
 #[derive(Parser)]
 #[command(
@@ -130,6 +137,10 @@ enum Command {
         /// Ignore merge commits messages
         #[arg(short, long)]
         ignore_merge_commits: bool,
+
+        /// Only check the commits of the given monorepo package
+        #[arg(long, value_parser = packages())]
+        package: Option<String>,
     },
 
     /// Create a new conventional commit
@@ -163,6 +174,10 @@ enum Command {
         /// Omit error on the commit log
         #[arg(short = 'e', long)]
         no_error: bool,
+
+        /// Only show the commits of the given monorepo package
+        #[arg(long, value_parser = packages())]
+        package: Option<String>,
     },
 
     /// Verify a single commit message
@@ -203,6 +218,10 @@ enum Command {
         /// Name of the repository used during template generation
         #[arg(long, requires_all = ["owner", "remote"])]
         repository: Option<String>,
+
+        /// Generate the changelog of the given monorepo package, with the package template
+        #[arg(long, value_parser = packages())]
+        package: Option<String>,
     },
 
     /// Get current version
@@ -214,6 +233,14 @@ enum Command {
         /// Specify which package to get the version for in a monorepo.
         #[arg(long, value_parser = packages())]
         package: Option<String>,
+
+        /// Preview the version the next auto bump would create
+        #[arg(long, conflicts_with = "fallback")]
+        next: bool,
+
+        /// Output format of the next version preview
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text, requires = "next")]
+        format: OutputFormat,
     },
 
     /// Commit changelog from latest tag to HEAD and create new tag
@@ -255,9 +282,49 @@ enum Command {
         #[arg(short = 'A', long)]
         annotated: Option<String>,
 
-        /// Dry-run: print the target version. No action taken
+        /// Dry-run: print the target version and the bump plan. No action taken
         #[arg(short, long)]
         dry_run: bool,
+
+        /// Increment to apply when no commit requires a version bump
+        #[arg(long, value_parser = ["patch", "minor"], requires = "auto")]
+        default_increment: Option<String>,
+
+        /// Set the build metadata, supports `{{sha}}`, `{{short_sha}}` and `{{date}}` placeholders
+        #[arg(long)]
+        build: Option<String>,
+
+        /// Revision (sha, tag...) the commit history and changelog start from
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Version to bump from, ex: when previous tags are not SemVer compliant
+        #[arg(long)]
+        previous_version: Option<String>,
+
+        /// Do not create the version commit, changes are left staged. Implies --no-tag
+        #[arg(long)]
+        no_commit: bool,
+
+        /// Do not create the version tag(s)
+        #[arg(long)]
+        no_tag: bool,
+
+        /// Do not write the changelog(s)
+        #[arg(long)]
+        no_changelog: bool,
+    },
+
+    /// Inspect monorepo packages
+    Packages {
+        #[command(subcommand)]
+        command: PackagesCommand,
+    },
+
+    /// Manage version tags
+    Tags {
+        #[command(subcommand)]
+        command: TagsCommand,
     },
 
     /// Install cog config files
@@ -286,6 +353,30 @@ enum Command {
     GenerateManpage { cmd: String },
 }
 
+#[derive(Subcommand)]
+enum TagsCommand {
+    /// Create tags with the current `tag_prefix` for tags using a legacy prefix
+    Migrate {
+        /// Print the tags to create. No action taken
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackagesCommand {
+    /// List the packages changed since their latest release and their next version
+    Changed {
+        /// Look for changes since this revision instead of each package latest tag
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Output format of the changed packages
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
 #[derive(Args)]
 struct CommitArgs {
     /// Conventional commit type
@@ -317,9 +408,18 @@ fn main() -> Result<()> {
     init_logs(cli.verbose, cli.quiet);
 
     match cli.command {
-        Command::GetVersion { fallback, package } => {
+        Command::GetVersion {
+            fallback,
+            package,
+            next,
+            format,
+        } => {
             let cocogitto = CocoGitto::get()?;
-            cocogitto.get_latest_version(fallback, package)?
+            if next {
+                cocogitto.get_next_version(package, format == OutputFormat::Json)?
+            } else {
+                cocogitto.get_latest_version(fallback, package)?
+            }
         }
         Command::Bump {
             version,
@@ -332,8 +432,27 @@ fn main() -> Result<()> {
             package,
             annotated,
             dry_run,
+            default_increment,
+            build,
+            from,
+            previous_version,
+            no_commit,
+            no_tag,
+            no_changelog,
         } => {
             let mut cocogitto = CocoGitto::get()?;
+            let options = BumpOptions {
+                default_increment: default_increment.map(|increment| match increment.as_str() {
+                    "minor" => Increment::Minor,
+                    _ => Increment::Patch,
+                }),
+                build,
+                from,
+                previous_version,
+                no_commit,
+                no_tag,
+                no_changelog,
+            };
             let is_monorepo = !SETTINGS.packages.is_empty();
 
             let increment = match version {
@@ -366,6 +485,7 @@ fn main() -> Result<()> {
                             hook_profile.as_deref(),
                             annotated,
                             dry_run,
+                            options,
                         )?
                     }
                     None => cocogitto.create_monorepo_version(
@@ -374,6 +494,7 @@ fn main() -> Result<()> {
                         hook_profile.as_deref(),
                         annotated,
                         dry_run,
+                        options,
                     )?,
                 }
             } else {
@@ -383,6 +504,7 @@ fn main() -> Result<()> {
                     hook_profile.as_deref(),
                     annotated,
                     dry_run,
+                    options,
                 )?
             }
         }
@@ -400,11 +522,12 @@ fn main() -> Result<()> {
         Command::Check {
             from_latest_tag,
             ignore_merge_commits,
+            package,
         } => {
             let cocogitto = CocoGitto::get()?;
             let from_latest_tag = from_latest_tag || SETTINGS.from_latest_tag;
             let ignore_merge_commits = ignore_merge_commits || SETTINGS.ignore_merge_commits;
-            cocogitto.check(from_latest_tag, ignore_merge_commits)?;
+            cocogitto.check(from_latest_tag, ignore_merge_commits, package.as_deref())?;
         }
         Command::Edit { from_latest_tag } => {
             let cocogitto = CocoGitto::get()?;
@@ -417,6 +540,7 @@ fn main() -> Result<()> {
             author,
             scope,
             no_error,
+            package,
         } => {
             let cocogitto = CocoGitto::get()?;
 
@@ -455,7 +579,7 @@ fn main() -> Result<()> {
 
             let filters = CommitFilters(filters);
 
-            let content = cocogitto.get_log(filters)?;
+            let content = cocogitto.get_log(filters, package.as_deref())?;
             output
                 .handle()?
                 .write_all(content.as_bytes())
@@ -468,25 +592,48 @@ fn main() -> Result<()> {
             remote,
             owner,
             repository,
+            package,
         } => {
             let cocogitto = CocoGitto::get()?;
 
-            let context = RemoteContext::try_new(remote, repository, owner)
-                .or_else(|| SETTINGS.get_template_context());
-            let template = template.as_ref().or(SETTINGS.changelog.template.as_ref());
-            let template = if let Some(template) = template {
-                Template::from_arg(template, context)?
-            } else {
-                Template::default()
-            };
+            let context = RemoteContext::try_new(remote, repository, owner);
 
             let pattern = pattern.as_deref().map(RevspecPattern::from);
 
-            let result = match at {
-                Some(at) => cocogitto.get_changelog_at_tag(&at, template)?,
-                None => {
-                    let changelog = cocogitto.get_changelog(pattern.unwrap_or_default(), true)?;
-                    changelog.into_markdown(template)?
+            let result = if let Some(package) = package {
+                let template = match template {
+                    Some(template) => {
+                        let context =
+                            context.or_else(|| SETTINGS.get_package_template_context(&package));
+                        Template::from_arg(&template, context)?
+                    }
+                    None => SETTINGS.get_package_changelog_template(&package)?,
+                };
+
+                match at {
+                    Some(at) => cocogitto.get_package_changelog_at_tag(&at, &package, template)?,
+                    None => cocogitto.render_package_changelog(
+                        pattern.unwrap_or_default(),
+                        &package,
+                        template,
+                    )?,
+                }
+            } else {
+                let context = context.or_else(|| SETTINGS.get_template_context());
+                let template = template.as_ref().or(SETTINGS.changelog.template.as_ref());
+                let template = if let Some(template) = template {
+                    Template::from_arg(template, context)?
+                } else {
+                    Template::default()
+                };
+
+                match at {
+                    Some(at) => cocogitto.get_changelog_at_tag(&at, template)?,
+                    None => {
+                        let changelog =
+                            cocogitto.get_changelog(pattern.unwrap_or_default(), true)?;
+                        changelog.into_markdown(template)?
+                    }
                 }
             };
             println!("{result}");
@@ -494,6 +641,20 @@ fn main() -> Result<()> {
         Command::Init { path } => {
             cocogitto::command::init::init(&path)?;
         }
+        Command::Packages { command } => {
+            let cocogitto = CocoGitto::get()?;
+            match command {
+                PackagesCommand::Changed { since, format } => {
+                    cocogitto.changed_packages(since, format == OutputFormat::Json)?
+                }
+            }
+        }
+        Command::Tags { command } => {
+            let cocogitto = CocoGitto::get()?;
+            match command {
+                TagsCommand::Migrate { dry_run } => cocogitto.migrate_tags(dry_run)?,
+            }
+        }
         Command::InstallHook { hook_type } => {
             let cocogitto = CocoGitto::get()?;
             match hook_type.as_str() {
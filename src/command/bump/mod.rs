@@ -1,30 +1,78 @@
 use crate::conventional::changelog::release::Release;
-use crate::conventional::commit::Commit;
-use crate::git::error::TagError;
+use crate::conventional::commit::{commit_type_increment, Commit};
+use crate::conventional::error::BumpError as ConventionalBumpError;
+use crate::conventional::version::{Increment, IncrementCommand};
+use crate::git::error::{Git2Error, TagError};
 use crate::git::hook::Hooks;
 use crate::git::oid::OidOf;
+use crate::git::repository::Repository;
 use crate::git::revspec::RevspecPattern;
 use crate::git::tag::Tag;
-use crate::hook::{Hook, HookVersion};
-use crate::settings::{HookType, MonoRepoPackage, Settings};
+use crate::hook::error::HookFailure;
+use crate::hook::run_log::HookLog;
+use crate::hook::{Hook, HookContext, HookVersion};
+use crate::settings::{self, BranchSettings, HookType, MonoRepoPackage, NoBumpCommits, Settings};
 use crate::BumpError;
-use crate::{CocoGitto, SETTINGS};
+use crate::{CocoGitto, CommitsMetadata, SETTINGS};
 use anyhow::Result;
 use anyhow::{anyhow, bail, ensure, Context};
+use chrono::Utc;
 use colored::Colorize;
 use conventional_commit_parser::commit::CommitType;
 use globset::Glob;
 use itertools::Itertools;
 use log::{error, info, warn};
+use semver::{BuildMetadata, Prerelease, VersionReq};
 use std::fmt;
 use std::fmt::Write;
+use std::path::PathBuf;
 use std::process::exit;
+use tera::Tera;
 
 mod monorepo;
 mod package;
+mod plan;
 mod standard;
 
-struct HookRunOptions<'a> {
+/// Additional bump options, mostly steps that can be skipped to let the CI create
+/// the commit and tag itself
+#[derive(Debug, Default, Clone)]
+pub struct BumpOptions {
+    /// Increment used by auto bumps when no commit requires a version bump,
+    /// takes precedence over the `bump.no_bump_commits` policy
+    pub default_increment: Option<Increment>,
+    /// Build metadata template of the created version(s), overrides `bump.build_metadata`
+    pub build: Option<String>,
+    /// Revision the commit history and changelog start from, instead of the latest tag
+    pub from: Option<String>,
+    /// Version to bump from, instead of the latest tag version
+    pub previous_version: Option<String>,
+    /// Do not create the version commit, implies `no_tag`
+    pub no_commit: bool,
+    /// Do not create the version tag(s)
+    pub no_tag: bool,
+    /// Do not write the changelog(s)
+    pub no_changelog: bool,
+}
+
+impl BumpOptions {
+    // Tags point to the version commit, there is nothing to tag without it
+    fn create_tag(&self) -> bool {
+        !self.no_tag && !self.no_commit
+    }
+
+    // Increment applied by auto bumps when no commit requires a version bump, if any
+    fn fallback_increment(&self) -> Option<Increment> {
+        self.default_increment
+            .or(match SETTINGS.bump.no_bump_commits {
+                NoBumpCommits::Patch => Some(Increment::Patch),
+                NoBumpCommits::Skip | NoBumpCommits::Error => None,
+            })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct HookRunOptions<'a> {
     hook_type: HookType,
     current_tag: Option<&'a HookVersion>,
     next_version: Option<&'a HookVersion>,
@@ -56,6 +104,27 @@ impl<'a> HookRunOptions<'a> {
         }
     }
 
+    pub fn bump_failure() -> Self {
+        Self {
+            hook_type: HookType::BumpFailure,
+            ..Self::pre_bump()
+        }
+    }
+
+    pub fn pre_commit() -> Self {
+        Self {
+            hook_type: HookType::PreCommit,
+            ..Self::pre_bump()
+        }
+    }
+
+    /// The same options for another kind of hooks, ex: the `post_changelog_hooks`
+    /// of a package bump
+    pub fn with_hook_type(mut self, hook_type: HookType) -> Self {
+        self.hook_type = hook_type;
+        self
+    }
+
     pub fn current_tag<'b>(mut self, version: Option<&'b HookVersion>) -> Self
     where
         'b: 'a,
@@ -90,6 +159,103 @@ impl<'a> HookRunOptions<'a> {
     }
 }
 
+/// Release channel of the current branch, as configured in the `[branches]` section
+pub(crate) struct ReleaseChannel {
+    branch: String,
+    settings: &'static BranchSettings,
+}
+
+impl ReleaseChannel {
+    fn ensure_in_range(&self, tag: &Tag) -> Result<()> {
+        let range = match &self.settings.range {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+
+        let requirement = VersionReq::parse(range).with_context(|| {
+            format!(
+                "Invalid version range `{range}` for branch '{}'",
+                self.branch
+            )
+        })?;
+
+        let mut version = tag.version.clone();
+        version.pre = Prerelease::EMPTY;
+        version.build = BuildMetadata::EMPTY;
+
+        ensure!(
+            requirement.matches(&version),
+            "Version {} is outside of the range `{}` allowed on branch '{}'",
+            tag.version,
+            range,
+            self.branch
+        );
+
+        Ok(())
+    }
+}
+
+impl HookRunOptions<'_> {
+    /// Values available to the hooks DSL, `if` conditions and environment
+    fn hook_context(&self, repository: &Repository) -> HookContext {
+        let repo_dir = repository.get_repo_dir();
+        let increment = self.next_version.and_then(|next| {
+            let current = self
+                .current_tag
+                .map(|current| current.prefixed_tag.clone())
+                .unwrap_or_default();
+            next.prefixed_tag.get_increment_from(&current)
+        });
+
+        let absolute = |path: PathBuf| match repo_dir {
+            Some(repo_dir) => repo_dir.join(path),
+            None => path,
+        };
+
+        let changelog = match self.package {
+            Some(package) => package.changelog_path(),
+            None => settings::changelog_path().clone(),
+        };
+
+        HookContext {
+            increment,
+            version: self
+                .next_version
+                .map(|version| Tag::format_version(&version.prefixed_tag.version)),
+            latest: self
+                .current_tag
+                .map(|version| Tag::format_version(&version.prefixed_tag.version)),
+            tag: self
+                .next_version
+                .map(|version| version.prefixed_tag.to_string()),
+            package: self.package_name.map(str::to_string),
+            package_path: self.package.map(|package| absolute(package.path.clone())),
+            commit_sha: repository
+                .get_head_commit_oid()
+                .ok()
+                .map(|oid| oid.to_string()),
+            branch: repository.get_branch_shorthand(),
+            changelog: Some(absolute(changelog)).filter(|changelog| changelog.exists()),
+        }
+    }
+
+    /// Hook stage name displayed in logs, ex: `pre-bump` or `post-bump-{package}`
+    fn label(&self) -> String {
+        let hook_type = match self.hook_type {
+            HookType::PreBump => "pre-bump",
+            HookType::PostBump => "post-bump",
+            HookType::PreCommit => "pre-commit",
+            HookType::PostChangelog => "post-changelog",
+            HookType::BumpFailure => "bump-failure",
+        };
+
+        match self.package_name {
+            None => hook_type.to_string(),
+            Some(package_name) => format!("{hook_type}-{package_name}"),
+        }
+    }
+}
+
 fn ensure_tag_is_greater_than_previous(current: &Tag, next: &Tag) -> Result<()> {
     if next <= current {
         let comparison = format!("{current} <= {next}").red();
@@ -105,33 +271,125 @@ fn ensure_tag_is_greater_than_previous(current: &Tag, next: &Tag) -> Result<()>
 fn tag_or_fallback_to_zero(tag: Result<Tag, TagError>) -> Result<Tag> {
     match tag {
         Ok(ref tag) => Ok(tag.clone()),
-        Err(ref err) if err == &TagError::NoTag => Ok(Tag::default()),
+        Err(TagError::NoTag) => Ok(Tag::default()),
         Err(err) => Err(anyhow!(err)),
     }
 }
 
 impl CocoGitto {
     pub fn unwrap_or_stash_and_exit<T>(&mut self, tag: &Tag, result: Result<T>) -> T {
+        self.unwrap_or_stash_and_run_failure_hooks(tag, result, HookRunOptions::bump_failure())
+    }
+
+    /// Like [`CocoGitto::unwrap_or_stash_and_exit`], running the `on_bump_failure_hooks`
+    /// matching the given options once the changes are stashed
+    fn unwrap_or_stash_and_run_failure_hooks<T>(
+        &mut self,
+        tag: &Tag,
+        result: Result<T>,
+        failure_hooks: HookRunOptions,
+    ) -> T {
         match result {
             Ok(res) => res,
             Err(err) => {
                 self.repository
                     .stash_failed_version(tag.clone())
                     .expect("stash");
+                let hook_failure = err
+                    .chain()
+                    .find_map(|cause| cause.downcast_ref::<HookFailure>());
+
                 error!(
                     "{}",
                     BumpError {
-                        cause: err.to_string(),
+                        cause: format!("{err:#}"),
                         version: tag.to_string(),
                         stash_number: 0,
+                        hook_output: hook_failure
+                            .map(|failure| failure.output_tail.clone())
+                            .unwrap_or_default(),
+                        hook_log: hook_failure.and_then(|failure| failure.log.clone()),
                     }
                 );
 
+                let failure_hooks = failure_hooks.with_hook_type(HookType::BumpFailure);
+                if let Err(err) = self.run_hooks(failure_hooks) {
+                    error!("{err:#}");
+                }
+
                 exit(1);
             }
         }
     }
 
+    /// Bump the given tag, applying the `--default-increment` or the `bump.no_bump_commits`
+    /// policy when no commit requires a version bump. `None` means the bump is skipped.
+    pub(crate) fn bump_or_fallback(
+        &self,
+        tag: &Tag,
+        increment: IncrementCommand,
+        options: &BumpOptions,
+    ) -> Result<Option<Tag>> {
+        match tag.bump(increment, &self.repository) {
+            Err(ConventionalBumpError::NoCommitFound) => {
+                let fallback = match options.fallback_increment() {
+                    Some(increment) => increment,
+                    None if SETTINGS.bump.no_bump_commits == NoBumpCommits::Skip => {
+                        info!("No commit found to bump current version, skipping");
+                        return Ok(None);
+                    }
+                    None => return Err(ConventionalBumpError::NoCommitFound.into()),
+                };
+
+                info!("No commit found to bump current version, applying a {fallback} bump");
+                Ok(Some(tag.bump(fallback.into(), &self.repository)?))
+            }
+            result => Ok(Some(result?)),
+        }
+    }
+
+    /// Render the build metadata given with `--build` or configured in `bump.build_metadata`,
+    /// ex: `{{short_sha}}.{{date}}` -> `a1b2c3d.20230412`
+    fn get_build_metadata(&self, options: &BumpOptions) -> Result<BuildMetadata> {
+        let template = match options
+            .build
+            .as_ref()
+            .or(SETTINGS.bump.build_metadata.as_ref())
+        {
+            Some(template) => template,
+            None => return Ok(BuildMetadata::EMPTY),
+        };
+
+        let sha = self.repository.get_head_commit_oid()?.to_string();
+        let mut context = tera::Context::new();
+        context.insert("sha", &sha);
+        context.insert("short_sha", &sha[0..7]);
+        context.insert("date", &Utc::now().format("%Y%m%d").to_string());
+
+        let build = Tera::one_off(template, &context, false)
+            .with_context(|| format!("Cannot render build metadata `{template}`"))?;
+
+        BuildMetadata::new(&build).with_context(|| format!("Invalid build metadata `{build}`"))
+    }
+
+    /// Create the version commit unless disabled. When the changelog is skipped there
+    /// might be nothing to commit, the version is then tagged on the current HEAD.
+    fn commit_version(&mut self, message: &str, options: &BumpOptions) -> Result<()> {
+        if options.no_commit {
+            return Ok(());
+        }
+
+        let sign = self.repository.gpg_sign();
+        match self.repository.commit(message, sign) {
+            Ok(_) => Ok(()),
+            Err(Git2Error::NothingToCommit { .. }) if options.no_changelog => {
+                info!("Nothing to commit, the version will be tagged on HEAD");
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
     fn pre_bump_checks(&mut self) -> Result<()> {
         if *SETTINGS == Settings::default() {
             let part1 = "Warning: using".yellow();
@@ -170,12 +428,79 @@ impl CocoGitto {
         Ok(())
     }
 
+    pub(crate) fn release_channel(&self) -> Option<ReleaseChannel> {
+        let branch = self.repository.get_branch_shorthand()?;
+        let settings = SETTINGS.branch_settings(&branch)?;
+        Some(ReleaseChannel { branch, settings })
+    }
+
+    /// Get the tag to bump from. Release channels bump from the latest stable version
+    /// so pre-releases of the same version share their base.
+    pub(crate) fn get_bump_origin(
+        &self,
+        package: Option<&str>,
+        channel: Option<&ReleaseChannel>,
+    ) -> Result<Tag> {
+        let tag = match (package, channel) {
+            (Some(package), Some(_)) => self.repository.get_latest_stable_package_tag(package),
+            (Some(package), None) => self.repository.get_latest_package_tag(package),
+            (None, Some(_)) => self.repository.get_latest_stable_tag(),
+            (None, None) => self.repository.get_latest_tag(),
+        };
+
+        tag_or_fallback_to_zero(tag)
+    }
+
+    /// Override the bump starting point with `--previous-version` and `--from`
+    pub(crate) fn seed_bump_origin(&self, mut origin: Tag, options: &BumpOptions) -> Result<Tag> {
+        if let Some(version) = &options.previous_version {
+            origin.version = Tag::parse_version(version, version)?;
+        }
+
+        if let Some(from) = &options.from {
+            let oid = self
+                .repository
+                .resolve_commit(from)
+                .with_context(|| format!("Cannot resolve revision `{from}`"))?;
+            origin.oid = Some(oid);
+        }
+
+        Ok(origin)
+    }
+
+    /// Apply the pre-release identifier, either given by the user or taken from the branch
+    /// release channel (ex: `beta.2`), and ensure the version is allowed on this branch.
+    pub(crate) fn apply_release_channel(
+        &self,
+        tag: &mut Tag,
+        package: Option<&str>,
+        pre_release: Option<&str>,
+        channel: Option<&ReleaseChannel>,
+    ) -> Result<()> {
+        let channel_identifier = channel.and_then(|channel| channel.settings.prerelease.as_deref());
+
+        if let Some(pre_release) = pre_release {
+            tag.version.pre = Prerelease::new(pre_release)?;
+        } else if let Some(identifier) = channel_identifier {
+            let number =
+                self.repository
+                    .next_prerelease_number(&tag.version, package, identifier)?;
+            tag.version.pre = Prerelease::new(&format!("{identifier}.{number}"))?;
+        }
+
+        if let Some(channel) = channel {
+            channel.ensure_in_range(tag)?;
+        }
+
+        Ok(())
+    }
+
     /// The target version is not created yet when generating the changelog.
     pub fn get_changelog_with_target_version(
         &self,
         pattern: RevspecPattern,
         tag: Tag,
-    ) -> Result<Release> {
+    ) -> Result<Release<'_>> {
         let commit_range = self.repository.get_commit_range(&pattern)?;
 
         let mut release = Release::from(commit_range);
@@ -189,12 +514,12 @@ impl CocoGitto {
         pattern: RevspecPattern,
         tag: Tag,
         package: &str,
-    ) -> Result<Release> {
+    ) -> Result<Release<'_>> {
         let commit_range = self
             .repository
             .get_commit_range_for_package(&pattern, package)?;
 
-        let mut release = Release::from(commit_range);
+        let mut release = Release::from(commit_range).with_package_commit_types(package);
         release.version = OidOf::Tag(tag);
         Ok(release)
     }
@@ -204,7 +529,7 @@ impl CocoGitto {
         &self,
         pattern: RevspecPattern,
         tag: Tag,
-    ) -> Result<Release> {
+    ) -> Result<Release<'_>> {
         let commit_range = self
             .repository
             .get_commit_range_for_monorepo_global(&pattern)?;
@@ -214,14 +539,15 @@ impl CocoGitto {
         Ok(release)
     }
 
-    fn run_hooks(&self, options: HookRunOptions) -> Result<()> {
+    /// Parse the hooks matching the given options and insert their versions
+    fn get_hooks(&self, options: &HookRunOptions, context: &HookContext) -> Result<Vec<Hook>> {
         let settings = Settings::get(&self.repository)?;
 
-        let hooks: Vec<Hook> = match (options.package, options.hook_profile) {
+        let mut hooks: Vec<Hook> = match (options.package, options.hook_profile) {
             (None, Some(profile)) => settings
                 .get_profile_hooks(profile, options.hook_type)
                 .iter()
-                .map(|s| s.parse())
+                .map(Hook::try_from)
                 .enumerate()
                 .map(|(idx, result)| {
                     result.context(format!(
@@ -235,7 +561,7 @@ impl CocoGitto {
 
                 hooks
                     .iter()
-                    .map(|s| s.parse())
+                    .map(Hook::try_from)
                     .enumerate()
                     .map(|(idx, result)| {
                         result.context(format!(
@@ -247,42 +573,55 @@ impl CocoGitto {
             (Some(package), None) => package
                 .get_hooks(options.hook_type)
                 .iter()
-                .map(|s| s.parse())
+                .map(Hook::try_from)
                 .enumerate()
                 .map(|(idx, result)| result.context(format!("Cannot parse hook at index {idx}")))
                 .try_collect()?,
             (None, None) => settings
                 .get_hooks(options.hook_type)
                 .iter()
-                .map(|s| s.parse())
+                .map(Hook::try_from)
                 .enumerate()
                 .map(|(idx, result)| result.context(format!("Cannot parse hook at index {idx}")))
                 .try_collect()?,
         };
 
+        for hook in &mut hooks {
+            hook.insert_versions(options.current_tag, options.next_version, context)?;
+        }
+
+        Ok(hooks)
+    }
+
+    // `hook_log` relative to the repository root, or `.git/cog/hooks.log`.
+    // An empty `hook_log` disables the capture of hooks output.
+    fn hook_log(&self) -> Option<HookLog> {
+        let path = match (&SETTINGS.hook_log, self.repository.get_repo_dir()) {
+            (Some(path), _) if path.as_os_str().is_empty() => return None,
+            (Some(path), Some(repo_dir)) => repo_dir.join(path),
+            (Some(path), None) => path.clone(),
+            (None, _) => self.repository.0.path().join("cog").join("hooks.log"),
+        };
+
+        Some(HookLog::new(path))
+    }
+
+    pub(crate) fn run_hooks(&self, options: HookRunOptions) -> Result<()> {
+        let context = options.hook_context(&self.repository);
+        let hooks = self.get_hooks(&options, &context)?;
+        let log = self.hook_log();
+
         if !hooks.is_empty() {
-            let hook_type = match options.hook_type {
-                HookType::PreBump => "pre-bump",
-                HookType::PostBump => "post-bump",
-            };
+            let msg = format!("[{}]", options.label()).underline().white().bold();
+            info!("{msg}")
+        }
 
-            match options.package_name {
-                None => {
-                    let msg = format!("[{hook_type}]").underline().white().bold();
-                    info!("{msg}")
-                }
-                Some(package_name) => {
-                    let msg = format!("[{hook_type}-{package_name}]")
-                        .underline()
-                        .white()
-                        .bold();
-                    info!("{msg}")
-                }
+        for hook in hooks {
+            if !hook.is_enabled(&context) {
+                info!("[{hook}] skipped, `if` condition not met");
+                continue;
             }
-        }
 
-        for mut hook in hooks {
-            hook.insert_versions(options.current_tag, options.next_version)?;
             let command = hook.to_string();
             let command = if command.chars().count() > 78 {
                 &command[0..command.len()]
@@ -290,8 +629,8 @@ impl CocoGitto {
                 &command
             };
             info!("[{command}]");
-            let package_path = options.package.map(|p| p.path.as_path());
-            hook.run(package_path).context(hook.to_string())?;
+            hook.run_with_log(&context, log.as_ref())
+                .context(hook.to_string())?;
             println!();
         }
 
@@ -299,10 +638,9 @@ impl CocoGitto {
     }
 
     fn get_revspec_for_tag(&mut self, tag: &Tag) -> Result<RevspecPattern> {
-        let origin = if tag.is_zero() {
-            self.repository.get_first_commit()?.to_string()
-        } else {
-            tag.oid_unchecked().to_string()
+        let origin = match tag.oid() {
+            Some(oid) => oid.to_string(),
+            None => self.repository.get_first_commit()?.to_string(),
         };
 
         let target = self.repository.get_head_commit_oid()?.to_string();
@@ -312,22 +650,24 @@ impl CocoGitto {
 }
 
 impl Release<'_> {
-    fn pretty_print_bump_summary(&self) -> Result<(), fmt::Error> {
+    fn pretty_print_bump_summary(&self, commit_types: &CommitsMetadata) -> Result<(), fmt::Error> {
         let conventional_commits: Vec<&Commit> = self
             .commits
             .iter()
             .map(|ch_commit| &ch_commit.commit)
             .collect();
 
-        // Commits which type are neither feat, fix nor breaking changes
+        let is_bump_commit = |commit: &Commit| {
+            commit.message.is_breaking_change
+                || commit_type_increment(commit_types, &commit.message.commit_type).is_some()
+        };
+
+        // Commits which type has no bump rule and are not breaking changes
         // won't affect the version number.
         let mut non_bump_commits: Vec<&CommitType> = conventional_commits
             .iter()
-            .filter_map(|commit| match &commit.message.commit_type {
-                CommitType::Feature | CommitType::BugFix => None,
-                _commit_type if commit.message.is_breaking_change => None,
-                _ => Some(&commit.message.commit_type),
-            })
+            .filter(|commit| !is_bump_commit(commit))
+            .map(|commit| &commit.message.commit_type)
             .collect();
 
         non_bump_commits.sort();
@@ -346,14 +686,9 @@ impl Release<'_> {
             info!("{}", skip_message);
         }
 
-        let bump_commits =
-            conventional_commits
-                .iter()
-                .filter(|commit| match &commit.message.commit_type {
-                    CommitType::Feature | CommitType::BugFix => true,
-                    _commit_type if commit.message.is_breaking_change => true,
-                    _ => false,
-                });
+        let bump_commits = conventional_commits
+            .iter()
+            .filter(|commit| is_bump_commit(commit));
 
         for commit in bump_commits {
             match &commit.message.commit_type {
@@ -369,7 +704,11 @@ impl Release<'_> {
                     info!("\tFound feature commit {}", commit.shorthand().blue())
                 }
                 CommitType::BugFix => info!("\tFound bug fix commit {}", commit.shorthand().blue()),
-                _ => (),
+                commit_type => info!(
+                    "\tFound {} commit {}",
+                    commit_type.as_ref().yellow(),
+                    commit.shorthand().blue()
+                ),
             }
         }
 
@@ -1,5 +1,7 @@
+use crate::command::bump::plan::BumpPlan;
 use crate::command::bump::{
-    ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero, HookRunOptions,
+    ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero, BumpOptions, HookRunOptions,
+    ReleaseChannel,
 };
 
 use crate::conventional::changelog::template::{
@@ -11,12 +13,13 @@ use crate::conventional::version::{Increment, IncrementCommand};
 
 use crate::git::tag::Tag;
 use crate::hook::HookVersion;
+use crate::settings::{HookType, MonoRepoVersioning, NoBumpCommits};
 use crate::{settings, CocoGitto, SETTINGS};
-use anyhow::Result;
+use anyhow::{anyhow, bail, ensure, Result};
 use colored::*;
 
 use log::{info, warn};
-use semver::Prerelease;
+use semver::{BuildMetadata, Version};
 use tera::Tera;
 
 use crate::conventional::error::BumpError;
@@ -29,6 +32,7 @@ struct PackageBumpData {
     old_version: Option<HookVersion>,
     new_version: HookVersion,
     increment: Increment,
+    bump_reason: Option<String>,
 }
 
 struct PackageData {
@@ -45,16 +49,44 @@ impl CocoGitto {
         hooks_config: Option<&str>,
         annotated: Option<String>,
         dry_run: bool,
+        options: BumpOptions,
     ) -> Result<()> {
+        ensure!(
+            options.from.is_none() && options.previous_version.is_none(),
+            "--from and --previous-version are only supported for single package bumps on a monorepo"
+        );
+
+        if SETTINGS.monorepo.versioning == MonoRepoVersioning::Fixed {
+            return self.create_monorepo_version_fixed(
+                increment,
+                pre_release,
+                hooks_config,
+                annotated,
+                dry_run,
+                &options,
+            );
+        }
+
         match increment {
             IncrementCommand::Auto => {
                 if SETTINGS.generate_mono_repository_global_tag {
-                    self.create_monorepo_version_auto(pre_release, hooks_config, annotated, dry_run)
+                    self.create_monorepo_version_auto(
+                        pre_release,
+                        hooks_config,
+                        annotated,
+                        dry_run,
+                        &options,
+                    )
                 } else {
                     if annotated.is_some() {
                         warn!("--annotated flag is not supported for package bumps without a global tag");
                     }
-                    self.create_all_package_version_auto(pre_release, hooks_config, dry_run)
+                    self.create_all_package_version_auto(
+                        pre_release,
+                        hooks_config,
+                        dry_run,
+                        options,
+                    )
                 }
             }
             _ => self.create_monorepo_version_manual(
@@ -63,6 +95,7 @@ impl CocoGitto {
                 hooks_config,
                 annotated,
                 dry_run,
+                &options,
             ),
         }
     }
@@ -72,30 +105,66 @@ impl CocoGitto {
         pre_release: Option<&str>,
         hooks_config: Option<&str>,
         dry_run: bool,
+        options: BumpOptions,
     ) -> Result<()> {
         self.pre_bump_checks()?;
+        let channel = self.release_channel();
         // Get package bumps
-        let bumps = self.get_packages_bumps(pre_release)?;
+        let build = self.get_build_metadata(&options)?;
+        let bumps = self.get_packages_bumps(pre_release, channel.as_ref(), &build, &options)?;
+
+        if bumps.is_empty() {
+            if SETTINGS.bump.no_bump_commits == NoBumpCommits::Skip {
+                info!("No commit found to bump packages, skipping");
+                return Ok(());
+            }
+
+            return Err(BumpError::NoCommitFound.into());
+        }
+
+        let commit_message = "chore(version): bump packages";
 
         if dry_run {
+            let mut plan = BumpPlan::default();
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::pre_bump().hook_profile(hooks_config),
+            )?;
+            self.plan_packages(&mut plan, hooks_config, &bumps, &options)?;
+            if !options.no_commit {
+                plan.commit(commit_message);
+            }
+            if options.create_tag() {
+                for bump in &bumps {
+                    plan.tag(&bump.new_version.prefixed_tag);
+                }
+            }
+            self.plan_packages_post_hooks(&mut plan, hooks_config, &bumps)?;
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::post_bump().hook_profile(hooks_config),
+            )?;
+            plan.print()?;
+
             for bump in bumps {
                 println!("{}", bump.new_version.prefixed_tag)
             }
             return Ok(());
         }
 
-        let hook_result = self.run_hooks(HookRunOptions::pre_bump().hook_profile(hooks_config));
+        let hook_options = HookRunOptions::pre_bump().hook_profile(hooks_config);
+        let hook_result = self.run_hooks(hook_options);
 
         self.repository.add_all()?;
-        self.unwrap_or_stash_and_exit(&Tag::default(), hook_result);
-        self.bump_packages(pre_release, hooks_config, &bumps)?;
+        self.unwrap_or_stash_and_run_failure_hooks(&Tag::default(), hook_result, hook_options);
+        self.bump_packages(hooks_config, &bumps, &options)?;
 
-        let sign = self.repository.gpg_sign();
-        self.repository
-            .commit("chore(version): bump packages", sign)?;
+        self.commit_version(commit_message, &options)?;
 
-        for bump in &bumps {
-            self.repository.create_tag(&bump.new_version.prefixed_tag)?;
+        if options.create_tag() {
+            for bump in &bumps {
+                self.repository.create_tag(&bump.new_version.prefixed_tag)?;
+            }
         }
 
         // Run per package post hooks
@@ -126,40 +195,49 @@ impl CocoGitto {
         hooks_config: Option<&str>,
         annotated: Option<String>,
         dry_run: bool,
+        options: &BumpOptions,
     ) -> Result<()> {
         self.pre_bump_checks()?;
+        let channel = self.release_channel();
         // Get package bumps
-        let bumps = self.get_packages_bumps(pre_release)?;
-
-        // Get the greatest package increment among public api packages
-        let increment_from_package_bumps = bumps
-            .iter()
-            .filter(|bump| bump.public_api)
-            .map(|bump| bump.increment)
-            .max();
+        let build = self.get_build_metadata(options)?;
+        let bumps = self.get_packages_bumps(pre_release, channel.as_ref(), &build, options)?;
 
         // Get current global tag
-        let old = self.repository.get_latest_tag();
-        let old = tag_or_fallback_to_zero(old)?;
-        let mut tag = old.bump(
-            IncrementCommand::AutoMonoRepoGlobal(increment_from_package_bumps),
-            &self.repository,
-        )?;
-        ensure_tag_is_greater_than_previous(&old, &tag)?;
-
-        if let Some(pre_release) = pre_release {
-            tag.version.pre = Prerelease::new(pre_release)?;
-        }
+        let origin = self.get_bump_origin(None, channel.as_ref())?;
+        let increment = global_increment(&bumps);
+        let mut tag = match self.bump_or_fallback(&origin, increment, options)? {
+            Some(tag) => tag,
+            None => return Ok(()),
+        };
+        ensure_tag_is_greater_than_previous(&origin, &tag)?;
+        self.apply_release_channel(&mut tag, None, pre_release, channel.as_ref())?;
+        tag.version.build = build;
+
+        self.release_monorepo_version(
+            tag.version,
+            bumps,
+            hooks_config,
+            annotated,
+            dry_run,
+            options,
+        )
+    }
 
-        let tag = Tag::create(tag.version, None);
+    // Write the global and packages changelogs, run the hooks then commit and tag the
+    // global version along with the given package bumps
+    fn release_monorepo_version(
+        &mut self,
+        version: Version,
+        bumps: Vec<PackageBumpData>,
+        hooks_config: Option<&str>,
+        annotated: Option<String>,
+        dry_run: bool,
+        options: &BumpOptions,
+    ) -> Result<()> {
+        let old = tag_or_fallback_to_zero(self.repository.get_latest_tag())?;
 
-        if dry_run {
-            for bump in bumps {
-                println!("{}", bump.new_version.prefixed_tag)
-            }
-            print!("{tag}");
-            return Ok(());
-        }
+        let tag = Tag::create(version, None);
 
         let mut template_context = vec![];
         for bump in &bumps {
@@ -186,54 +264,97 @@ impl CocoGitto {
         let changelog =
             self.get_monorepo_global_changelog_with_target_version(pattern, tag.clone())?;
 
-        changelog.pretty_print_bump_summary()?;
-
         let path = settings::changelog_path();
         let template = SETTINGS.get_monorepo_changelog_template()?;
-
-        changelog.write_to_file(
-            path,
-            template,
-            ReleaseType::MonoRepo(MonoRepoContext {
-                package_lock: false,
-                packages: template_context,
-            }),
-        )?;
+        let context = ReleaseType::MonoRepo(MonoRepoContext {
+            package_lock: false,
+            packages: template_context,
+        });
 
         let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
         let next_version = HookVersion::new(tag.clone());
+        let commit_message = format!("chore(version): {}", next_version.prefixed_tag);
 
-        let hook_result = self.run_hooks(
-            HookRunOptions::pre_bump()
+        if dry_run {
+            let mut plan = BumpPlan::default();
+            plan.version(current.as_ref().map(|v| &v.prefixed_tag), &tag);
+            let hook_options = HookRunOptions::pre_bump()
                 .current_tag(current.as_ref())
                 .next_version(&next_version)
-                .hook_profile(hooks_config),
-        );
+                .hook_profile(hooks_config);
+
+            if !options.no_changelog {
+                plan.changelog(changelog, path, template, context)?;
+                self.plan_hooks(
+                    &mut plan,
+                    hook_options.with_hook_type(HookType::PostChangelog),
+                )?;
+            }
+            self.plan_hooks(&mut plan, hook_options)?;
+            self.plan_packages(&mut plan, hooks_config, &bumps, options)?;
+            if !options.no_commit {
+                plan.commit(&commit_message);
+            }
+            if options.create_tag() {
+                for bump in &bumps {
+                    plan.tag(&bump.new_version.prefixed_tag);
+                }
+                plan.tag(&tag);
+            }
+            self.plan_packages_post_hooks(&mut plan, hooks_config, &bumps)?;
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::post_bump()
+                    .current_tag(current.as_ref())
+                    .next_version(&next_version)
+                    .hook_profile(hooks_config),
+            )?;
+            plan.print()?;
+
+            for bump in bumps {
+                println!("{}", bump.new_version.prefixed_tag)
+            }
+            print!("{tag}");
+            return Ok(());
+        }
+
+        let hook_options = HookRunOptions::pre_bump()
+            .current_tag(current.as_ref())
+            .next_version(&next_version)
+            .hook_profile(hooks_config);
+
+        changelog.pretty_print_bump_summary(&SETTINGS.commit_types())?;
+        let hook_result = if !options.no_changelog {
+            changelog.write_to_file(path, template, context)?;
+            self.run_hooks(hook_options.with_hook_type(HookType::PostChangelog))
+        } else {
+            Ok(())
+        };
+
+        let hook_result = hook_result.and_then(|()| self.run_hooks(hook_options));
 
         self.repository.add_all()?;
 
-        self.unwrap_or_stash_and_exit(&tag, hook_result);
+        self.unwrap_or_stash_and_run_failure_hooks(&tag, hook_result, hook_options);
 
-        self.bump_packages(pre_release, hooks_config, &bumps)?;
+        self.bump_packages(hooks_config, &bumps, options)?;
 
-        let sign = self.repository.gpg_sign();
-        self.repository.commit(
-            &format!("chore(version): {}", next_version.prefixed_tag),
-            sign,
-        )?;
+        self.commit_version(&commit_message, options)?;
 
-        for bump in &bumps {
-            self.repository.create_tag(&bump.new_version.prefixed_tag)?;
-        }
+        if options.create_tag() {
+            for bump in &bumps {
+                self.repository.create_tag(&bump.new_version.prefixed_tag)?;
+            }
 
-        if let Some(msg_tmpl) = annotated {
-            let mut context = tera::Context::new();
-            context.insert("latest", &old.version.to_string());
-            context.insert("version", &tag.version.to_string());
-            let msg = Tera::one_off(&msg_tmpl, &context, false)?;
-            self.repository.create_annotated_tag(&tag, &msg)?;
-        } else {
-            self.repository.create_tag(&tag)?;
+            if let Some(msg_tmpl) = annotated {
+                let mut context = tera::Context::new();
+                context.insert("latest", &old.version.to_string());
+                context.insert("version", &tag.version.to_string());
+                let msg = Tera::one_off(&msg_tmpl, &context, false)?;
+                self.repository.create_annotated_tag(&tag, &msg)?;
+            } else {
+                self.repository.create_tag(&tag)?;
+            }
         }
 
         // Run per package post hooks
@@ -262,6 +383,62 @@ impl CocoGitto {
         Ok(())
     }
 
+    // Bump the global version from every commit and release all packages with it
+    fn create_monorepo_version_fixed(
+        &mut self,
+        increment: IncrementCommand,
+        pre_release: Option<&str>,
+        hooks_config: Option<&str>,
+        annotated: Option<String>,
+        dry_run: bool,
+        options: &BumpOptions,
+    ) -> Result<()> {
+        self.pre_bump_checks()?;
+        let channel = self.release_channel();
+
+        let origin = self.get_bump_origin(None, channel.as_ref())?;
+        let mut tag = match self.bump_or_fallback(&origin, increment, options)? {
+            Some(tag) => tag,
+            None => return Ok(()),
+        };
+        ensure_tag_is_greater_than_previous(&origin, &tag)?;
+        self.apply_release_channel(&mut tag, None, pre_release, channel.as_ref())?;
+        tag.version.build = self.get_build_metadata(options)?;
+        let increment = tag.get_increment_from(&origin).unwrap_or(Increment::Patch);
+
+        let mut bumps = vec![];
+        for package_name in packages_in_dependency_order()? {
+            let package = &SETTINGS.packages[package_name];
+            let old = self.repository.get_latest_package_tag(package_name);
+            let old = tag_or_fallback_to_zero(old)?;
+            let old_version = if old.is_zero() {
+                None
+            } else {
+                Some(HookVersion::new(old))
+            };
+
+            let new_version = Tag::create(tag.version.clone(), Some(package_name.to_string()));
+            bumps.push(PackageBumpData {
+                package_name: package_name.to_string(),
+                package_path: package.path.to_string_lossy().to_string(),
+                public_api: package.public_api,
+                old_version,
+                new_version: HookVersion::new(new_version),
+                increment,
+                bump_reason: None,
+            });
+        }
+
+        self.release_monorepo_version(
+            tag.version,
+            bumps,
+            hooks_config,
+            annotated,
+            dry_run,
+            options,
+        )
+    }
+
     fn create_monorepo_version_manual(
         &mut self,
         increment: IncrementCommand,
@@ -269,28 +446,23 @@ impl CocoGitto {
         hooks_config: Option<&str>,
         annotated: Option<String>,
         dry_run: bool,
+        options: &BumpOptions,
     ) -> Result<()> {
         self.pre_bump_checks()?;
+        let channel = self.release_channel();
         // Get package bumps
         let bumps = self.get_current_packages()?;
 
         // Get current global tag
-        let old = self.repository.get_latest_tag();
-        let old = tag_or_fallback_to_zero(old)?;
-        let mut tag = old.bump(increment, &self.repository)?;
-        ensure_tag_is_greater_than_previous(&old, &tag)?;
-
-        if let Some(pre_release) = pre_release {
-            tag.version.pre = Prerelease::new(pre_release)?;
-        }
+        let origin = self.get_bump_origin(None, channel.as_ref())?;
+        let mut tag = origin.bump(increment, &self.repository)?;
+        ensure_tag_is_greater_than_previous(&origin, &tag)?;
+        self.apply_release_channel(&mut tag, None, pre_release, channel.as_ref())?;
+        tag.version.build = self.get_build_metadata(options)?;
+        let old = tag_or_fallback_to_zero(self.repository.get_latest_tag())?;
 
         let tag = Tag::create(tag.version, None);
 
-        if dry_run {
-            print!("{tag}");
-            return Ok(());
-        }
-
         let mut template_context = vec![];
         for bump in &bumps {
             template_context.push(PackageBumpContext {
@@ -305,47 +477,81 @@ impl CocoGitto {
         let changelog =
             self.get_monorepo_global_changelog_with_target_version(pattern, tag.clone())?;
 
-        changelog.pretty_print_bump_summary()?;
-
         let path = settings::changelog_path();
         let template = SETTINGS.get_monorepo_changelog_template()?;
-
-        changelog.write_to_file(
-            path,
-            template,
-            ReleaseType::MonoRepo(MonoRepoContext {
-                package_lock: true,
-                packages: template_context,
-            }),
-        )?;
+        let context = ReleaseType::MonoRepo(MonoRepoContext {
+            package_lock: true,
+            packages: template_context,
+        });
 
         let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
         let next_version = HookVersion::new(tag.clone());
+        let commit_message = format!("chore(version): {}", next_version.prefixed_tag);
 
-        let hook_result = self.run_hooks(
-            HookRunOptions::pre_bump()
+        if dry_run {
+            let mut plan = BumpPlan::default();
+            plan.version(current.as_ref().map(|v| &v.prefixed_tag), &tag);
+            let hook_options = HookRunOptions::pre_bump()
                 .current_tag(current.as_ref())
                 .next_version(&next_version)
-                .hook_profile(hooks_config),
-        );
-
-        self.repository.add_all()?;
-        self.unwrap_or_stash_and_exit(&tag, hook_result);
+                .hook_profile(hooks_config);
+
+            if !options.no_changelog {
+                plan.changelog(changelog, path, template, context)?;
+                self.plan_hooks(
+                    &mut plan,
+                    hook_options.with_hook_type(HookType::PostChangelog),
+                )?;
+            }
+            self.plan_hooks(&mut plan, hook_options)?;
+            if !options.no_commit {
+                plan.commit(&commit_message);
+            }
+            if options.create_tag() {
+                plan.tag(&tag);
+            }
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::post_bump()
+                    .current_tag(current.as_ref())
+                    .next_version(&next_version)
+                    .hook_profile(hooks_config),
+            )?;
+            plan.print()?;
+            print!("{tag}");
+            return Ok(());
+        }
 
-        let sign = self.repository.gpg_sign();
-        self.repository.commit(
-            &format!("chore(version): {}", next_version.prefixed_tag),
-            sign,
-        )?;
+        let hook_options = HookRunOptions::pre_bump()
+            .current_tag(current.as_ref())
+            .next_version(&next_version)
+            .hook_profile(hooks_config);
 
-        if let Some(msg_tmpl) = annotated {
-            let mut context = tera::Context::new();
-            context.insert("latest", &old.version.to_string());
-            context.insert("version", &tag.version.to_string());
-            let msg = Tera::one_off(&msg_tmpl, &context, false)?;
-            self.repository.create_annotated_tag(&tag, &msg)?;
+        changelog.pretty_print_bump_summary(&SETTINGS.commit_types())?;
+        let hook_result = if !options.no_changelog {
+            changelog.write_to_file(path, template, context)?;
+            self.run_hooks(hook_options.with_hook_type(HookType::PostChangelog))
         } else {
-            self.repository.create_tag(&tag)?;
+            Ok(())
+        };
+
+        let hook_result = hook_result.and_then(|()| self.run_hooks(hook_options));
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_run_failure_hooks(&tag, hook_result, hook_options);
+
+        self.commit_version(&commit_message, options)?;
+
+        if options.create_tag() {
+            if let Some(msg_tmpl) = annotated {
+                let mut context = tera::Context::new();
+                context.insert("latest", &old.version.to_string());
+                context.insert("version", &tag.version.to_string());
+                let msg = Tera::one_off(&msg_tmpl, &context, false)?;
+                self.repository.create_annotated_tag(&tag, &msg)?;
+            } else {
+                self.repository.create_tag(&tag)?;
+            }
         }
 
         // Run global post hooks
@@ -374,32 +580,107 @@ impl CocoGitto {
         Ok(packages)
     }
 
-    // Calculate all package bump
-    fn get_packages_bumps(&self, pre_release: Option<&str>) -> Result<Vec<PackageBumpData>> {
-        let mut package_bumps = vec![];
-        for (package_name, package) in SETTINGS.packages.iter() {
-            let old = self.repository.get_latest_package_tag(package_name);
-            let old = tag_or_fallback_to_zero(old)?;
+    /// The increment `cog bump --auto` computes the global version of an independent
+    /// monorepo with, from the commit history and the package bumps
+    pub(crate) fn monorepo_global_increment(
+        &self,
+        channel: Option<&ReleaseChannel>,
+        options: &BumpOptions,
+    ) -> Result<IncrementCommand> {
+        let bumps = self.get_packages_bumps(None, channel, &BuildMetadata::EMPTY, options)?;
+        Ok(global_increment(&bumps))
+    }
 
-            let next_version = old.bump(
+    // Calculate all package bump, dependencies come before their dependants
+    // Packages with commits since their latest tag but none requiring a version bump get the
+    // `--default-increment` or `bump.no_bump_commits` fallback increment, if any
+    fn get_packages_bumps(
+        &self,
+        pre_release: Option<&str>,
+        channel: Option<&ReleaseChannel>,
+        build: &BuildMetadata,
+        options: &BumpOptions,
+    ) -> Result<Vec<PackageBumpData>> {
+        let mut package_bumps: Vec<PackageBumpData> = vec![];
+        for package_name in packages_in_dependency_order()? {
+            let package = &SETTINGS.packages[package_name];
+            let origin = self.get_bump_origin(Some(package_name), channel)?;
+
+            let mut next_version = self.next_package_version(
+                &origin,
                 IncrementCommand::AutoPackage(package_name.to_string()),
-                &self.repository,
-            );
+                package_name,
+                pre_release,
+                channel,
+                build,
+            )?;
 
-            if let Err(BumpError::NoCommitFound) = next_version {
-                continue;
+            if let (None, Some(fallback)) = (&next_version, options.fallback_increment()) {
+                if !origin
+                    .commits_since(&self.repository, Some(package_name))?
+                    .is_empty()
+                {
+                    next_version = self.next_package_version(
+                        &origin,
+                        fallback.into(),
+                        package_name,
+                        pre_release,
+                        channel,
+                        build,
+                    )?;
+                }
             }
 
-            let mut next_version = next_version.unwrap();
+            let mut increment = next_version
+                .as_ref()
+                .and_then(|tag| tag.get_increment_from(&origin));
+
+            let bumped_dependencies: Vec<&str> = package
+                .depends_on
+                .iter()
+                .filter(|dependency| {
+                    package_bumps
+                        .iter()
+                        .any(|bump| &bump.package_name == *dependency)
+                })
+                .map(String::as_str)
+                .collect();
+
+            let bump_reason = match bumped_dependencies.as_slice() {
+                [] => None,
+                [dependency] => Some(format!("dependency {dependency} bumped")),
+                dependencies => Some(format!("dependencies {} bumped", dependencies.join(", "))),
+            };
+
+            if bump_reason.is_some() {
+                let minimum = SETTINGS
+                    .bump
+                    .dependency_increment
+                    .unwrap_or(Increment::Patch);
+
+                let below_minimum = match increment {
+                    Some(increment) => increment < minimum,
+                    None => true,
+                };
 
-            if let Some(pre_release) = pre_release {
-                next_version.version.pre = Prerelease::new(pre_release)?;
+                if below_minimum {
+                    next_version = self.next_package_version(
+                        &origin,
+                        minimum.into(),
+                        package_name,
+                        pre_release,
+                        channel,
+                        build,
+                    )?;
+                    increment = next_version
+                        .as_ref()
+                        .and_then(|tag| tag.get_increment_from(&origin));
+                }
             }
 
-            let tag = Tag::create(next_version.version, Some(package_name.to_string()));
-            let increment = tag.get_increment_from(&old);
-
-            if let Some(increment) = increment {
+            if let (Some(tag), Some(increment)) = (next_version, increment) {
+                let old = self.repository.get_latest_package_tag(package_name);
+                let old = tag_or_fallback_to_zero(old)?;
                 let old_version = if old.is_zero() {
                     None
                 } else {
@@ -413,6 +694,7 @@ impl CocoGitto {
                     old_version,
                     new_version: HookVersion::new(tag),
                     increment,
+                    bump_reason,
                 })
             }
         }
@@ -420,12 +702,38 @@ impl CocoGitto {
         Ok(package_bumps)
     }
 
+    // Bump a package from its origin, `None` when there is no commit to bump from
+    fn next_package_version(
+        &self,
+        origin: &Tag,
+        increment: IncrementCommand,
+        package_name: &str,
+        pre_release: Option<&str>,
+        channel: Option<&ReleaseChannel>,
+        build: &BuildMetadata,
+    ) -> Result<Option<Tag>> {
+        let next_version = origin.bump(increment, &self.repository);
+
+        if let Err(BumpError::NoCommitFound) = next_version {
+            return Ok(None);
+        }
+
+        let mut next_version = next_version?;
+        self.apply_release_channel(&mut next_version, Some(package_name), pre_release, channel)?;
+        next_version.version.build = build.clone();
+
+        Ok(Some(Tag::create(
+            next_version.version,
+            Some(package_name.to_string()),
+        )))
+    }
+
     // Run pre hooks and generate changelog for each package and git add the generated content
     fn bump_packages(
         &mut self,
-        pre_release: Option<&str>,
         hooks_config: Option<&str>,
         package_bumps: &Vec<PackageBumpData>,
+        options: &BumpOptions,
     ) -> Result<()> {
         for bump in package_bumps {
             let package_name = &bump.package_name;
@@ -439,17 +747,7 @@ impl CocoGitto {
 
             info!("{msg}");
 
-            let mut next_version = old.bump(
-                IncrementCommand::AutoPackage(package_name.to_string()),
-                &self.repository,
-            )?;
-            ensure_tag_is_greater_than_previous(&old, &next_version)?;
-
-            if let Some(pre_release) = pre_release {
-                next_version.version.pre = Prerelease::new(pre_release)?;
-            }
-
-            let tag = Tag::create(next_version.version, Some(package_name.to_string()));
+            let tag = bump.new_version.prefixed_tag.clone();
             let pattern = self.get_revspec_for_tag(&old)?;
 
             let package = SETTINGS
@@ -463,18 +761,16 @@ impl CocoGitto {
                 package_name.as_str(),
             )?;
 
-            changelog.pretty_print_bump_summary()?;
+            changelog.pretty_print_bump_summary(&SETTINGS.package_commit_types(package_name))?;
 
             let path = package.changelog_path();
-            let template = SETTINGS.get_package_changelog_template()?;
+            let template = SETTINGS.get_package_changelog_template(package_name)?;
 
             let additional_context = ReleaseType::Package(PackageContext {
                 package_name: package_name.as_ref(),
+                bump_reason: bump.bump_reason.as_deref(),
             });
 
-            changelog.write_to_file(&path, template, additional_context)?;
-            info!("\tChangelog updated {:?}", path);
-
             let old_version = self
                 .repository
                 .get_latest_package_tag(package_name)
@@ -483,18 +779,160 @@ impl CocoGitto {
 
             let new_version = HookVersion::new(tag.clone());
 
-            let hook_result = self.run_hooks(
-                HookRunOptions::pre_bump()
-                    .current_tag(old_version.as_ref())
-                    .next_version(&new_version)
-                    .hook_profile(hooks_config)
-                    .package(package_name, package),
-            );
+            let hook_options = HookRunOptions::pre_bump()
+                .current_tag(old_version.as_ref())
+                .next_version(&new_version)
+                .hook_profile(hooks_config)
+                .package(package_name, package);
+
+            let hook_result = if !options.no_changelog {
+                changelog.write_to_file(&path, template, additional_context)?;
+                info!("\tChangelog updated {:?}", path);
+                self.run_hooks(hook_options.with_hook_type(HookType::PostChangelog))
+            } else {
+                Ok(())
+            };
+
+            let hook_result = hook_result.and_then(|()| self.run_hooks(hook_options));
 
             self.repository.add_all()?;
-            self.unwrap_or_stash_and_exit(&tag, hook_result);
+            self.unwrap_or_stash_and_run_failure_hooks(&tag, hook_result, hook_options);
+        }
+
+        Ok(())
+    }
+
+    // Add the version, changelog and pre hooks of each package bump to the plan
+    fn plan_packages(
+        &mut self,
+        plan: &mut BumpPlan,
+        hooks_config: Option<&str>,
+        package_bumps: &Vec<PackageBumpData>,
+        options: &BumpOptions,
+    ) -> Result<()> {
+        for bump in package_bumps {
+            let package_name = &bump.package_name;
+            let package = SETTINGS
+                .packages
+                .get(package_name.as_str())
+                .expect("package exists");
+
+            let old = self.repository.get_latest_package_tag(package_name);
+            let old = tag_or_fallback_to_zero(old)?;
+            let pattern = self.get_revspec_for_tag(&old)?;
+            let changelog = self.get_package_changelog_with_target_version(
+                pattern,
+                bump.new_version.prefixed_tag.clone(),
+                package_name.as_str(),
+            )?;
+
+            let additional_context = ReleaseType::Package(PackageContext {
+                package_name: package_name.as_ref(),
+                bump_reason: bump.bump_reason.as_deref(),
+            });
+
+            plan.version(
+                bump.old_version.as_ref().map(|v| &v.prefixed_tag),
+                &bump.new_version.prefixed_tag,
+            );
+            let hook_options = HookRunOptions::pre_bump()
+                .current_tag(bump.old_version.as_ref())
+                .next_version(&bump.new_version)
+                .hook_profile(hooks_config)
+                .package(package_name, package);
+
+            if !options.no_changelog {
+                plan.changelog(
+                    changelog,
+                    package.changelog_path(),
+                    SETTINGS.get_package_changelog_template(package_name)?,
+                    additional_context,
+                )?;
+                self.plan_hooks(plan, hook_options.with_hook_type(HookType::PostChangelog))?;
+            }
+            self.plan_hooks(plan, hook_options)?;
+        }
+
+        Ok(())
+    }
+
+    fn plan_packages_post_hooks(
+        &self,
+        plan: &mut BumpPlan,
+        hooks_config: Option<&str>,
+        package_bumps: &Vec<PackageBumpData>,
+    ) -> Result<()> {
+        for bump in package_bumps {
+            let package = SETTINGS
+                .packages
+                .get(&bump.package_name)
+                .expect("package exists");
+
+            self.plan_hooks(
+                plan,
+                HookRunOptions::post_bump()
+                    .current_tag(bump.old_version.as_ref())
+                    .next_version(&bump.new_version)
+                    .hook_profile(hooks_config)
+                    .package(&bump.package_name, package),
+            )?;
         }
 
         Ok(())
     }
 }
+
+// The global version gets the greatest package increment among public api packages
+fn global_increment(bumps: &[PackageBumpData]) -> IncrementCommand {
+    let increment_from_package_bumps = bumps
+        .iter()
+        .filter(|bump| bump.public_api)
+        .map(|bump| bump.increment)
+        .max();
+
+    IncrementCommand::AutoMonoRepoGlobal(increment_from_package_bumps)
+}
+
+// Sort package names so that every package comes after the packages it depends on
+fn packages_in_dependency_order() -> Result<Vec<&'static str>> {
+    let mut names: Vec<&'static str> = SETTINGS.packages.keys().map(String::as_str).collect();
+    names.sort_unstable();
+
+    let mut ordered = Vec::with_capacity(names.len());
+    let mut visiting = vec![];
+    for name in names {
+        visit_package(name, &mut ordered, &mut visiting)?;
+    }
+
+    Ok(ordered)
+}
+
+fn visit_package(
+    name: &'static str,
+    ordered: &mut Vec<&'static str>,
+    visiting: &mut Vec<&'static str>,
+) -> Result<()> {
+    if ordered.contains(&name) {
+        return Ok(());
+    }
+
+    if visiting.contains(&name) {
+        bail!(
+            "Circular dependency between packages: {} -> {name}",
+            visiting.join(" -> ")
+        );
+    }
+
+    visiting.push(name);
+    for dependency in &SETTINGS.packages[name].depends_on {
+        let (dependency, _) = SETTINGS
+            .packages
+            .get_key_value(dependency)
+            .ok_or_else(|| anyhow!("Package `{name}` depends on unknown package `{dependency}`"))?;
+        visit_package(dependency, ordered, visiting)?;
+    }
+    visiting.pop();
+    ordered.push(name);
+
+    Ok(())
+}
@@ -1,20 +1,21 @@
+use crate::command::bump::plan::BumpPlan;
 use crate::command::bump::{
-    ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero, HookRunOptions,
+    ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero, BumpOptions, HookRunOptions,
 };
 use crate::conventional::changelog::template::PackageContext;
 use crate::conventional::changelog::ReleaseType;
 use crate::conventional::version::IncrementCommand;
 use crate::git::tag::Tag;
 use crate::hook::HookVersion;
-use crate::settings::MonoRepoPackage;
+use crate::settings::{HookType, MonoRepoPackage, MonoRepoVersioning};
 use crate::{CocoGitto, SETTINGS};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use colored::*;
 use log::info;
-use semver::Prerelease;
 use tera::Tera;
 
 impl CocoGitto {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_package_version(
         &mut self,
         (package_name, package): (&str, &MonoRepoPackage),
@@ -23,70 +24,142 @@ impl CocoGitto {
         hooks_config: Option<&str>,
         annotated: Option<String>,
         dry_run: bool,
+        options: BumpOptions,
     ) -> Result<()> {
+        ensure!(
+            SETTINGS.monorepo.versioning == MonoRepoVersioning::Independent,
+            "Packages cannot be bumped individually with fixed monorepo versioning"
+        );
         self.pre_bump_checks()?;
 
-        let current_tag = self.repository.get_latest_package_tag(package_name);
-        let current_tag = tag_or_fallback_to_zero(current_tag)?;
-        let mut next_version = current_tag.bump(increment, &self.repository)?;
+        let channel = self.release_channel();
+        let current_tag = self.get_bump_origin(Some(package_name), channel.as_ref())?;
+        let current_tag = self.seed_bump_origin(current_tag, &options)?;
+        let mut next_version = match self.bump_or_fallback(&current_tag, increment, &options)? {
+            Some(tag) => tag,
+            None => return Ok(()),
+        };
         ensure_tag_is_greater_than_previous(&current_tag, &next_version)?;
-        if let Some(pre_release) = pre_release {
-            next_version.version.pre = Prerelease::new(pre_release)?;
-        }
+        self.apply_release_channel(
+            &mut next_version,
+            Some(package_name),
+            pre_release,
+            channel.as_ref(),
+        )?;
+        next_version.version.build = self.get_build_metadata(&options)?;
 
         let tag = Tag::create(next_version.version.clone(), Some(package_name.to_string()));
 
-        if dry_run {
-            print!("{tag}");
-            return Ok(());
-        }
-
-        let pattern = self.get_revspec_for_tag(&current_tag)?;
+        let latest_tag = if options.from.is_some() || options.previous_version.is_some() {
+            current_tag.clone()
+        } else {
+            let latest_tag = self.repository.get_latest_package_tag(package_name);
+            tag_or_fallback_to_zero(latest_tag)?
+        };
+        let pattern = self.get_revspec_for_tag(&latest_tag)?;
 
         let changelog =
             self.get_package_changelog_with_target_version(pattern, tag.clone(), package_name)?;
 
-        changelog.pretty_print_bump_summary()?;
-
         let path = package.changelog_path();
-        let template = SETTINGS.get_package_changelog_template()?;
-        let additional_context = ReleaseType::Package(PackageContext { package_name });
-        changelog.write_to_file(path, template, additional_context)?;
-
-        let current = self
-            .repository
-            .get_latest_package_tag(package_name)
-            .map(HookVersion::new)
-            .ok();
+        let template = SETTINGS.get_package_changelog_template(package_name)?;
+        let additional_context = ReleaseType::Package(PackageContext {
+            package_name,
+            bump_reason: None,
+        });
+
+        let current = if options.previous_version.is_some() {
+            let version = current_tag.version.clone();
+            Some(HookVersion::new(Tag::create(
+                version,
+                Some(package_name.to_string()),
+            )))
+        } else {
+            self.repository
+                .get_latest_package_tag(package_name)
+                .map(HookVersion::new)
+                .ok()
+        };
 
         let next_version = HookVersion::new(Tag::create(
             next_version.version,
             Some(package_name.to_string()),
         ));
+        let commit_message = format!("chore(version): {tag}");
 
-        let hook_result = self.run_hooks(
-            HookRunOptions::pre_bump()
-                .current_tag(current.as_ref())
-                .next_version(&next_version)
-                .hook_profile(hooks_config)
-                .package(package_name, package),
-        );
+        if dry_run {
+            let mut plan = BumpPlan::default();
+            plan.version(current.as_ref().map(|v| &v.prefixed_tag), &tag);
+            if !options.no_changelog {
+                plan.changelog(changelog, path, template, additional_context)?;
+                self.plan_hooks(
+                    &mut plan,
+                    HookRunOptions::pre_bump()
+                        .with_hook_type(HookType::PostChangelog)
+                        .current_tag(current.as_ref())
+                        .next_version(&next_version)
+                        .hook_profile(hooks_config)
+                        .package(package_name, package),
+                )?;
+            }
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::pre_bump()
+                    .current_tag(current.as_ref())
+                    .next_version(&next_version)
+                    .hook_profile(hooks_config)
+                    .package(package_name, package),
+            )?;
+            if !options.no_commit {
+                plan.commit(&commit_message);
+            }
+            if options.create_tag() {
+                plan.tag(&tag);
+            }
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::post_bump()
+                    .current_tag(current.as_ref())
+                    .next_version(&next_version)
+                    .hook_profile(hooks_config)
+                    .package(package_name, package),
+            )?;
+            plan.print()?;
+            print!("{tag}");
+            return Ok(());
+        }
 
-        self.repository.add_all()?;
-        self.unwrap_or_stash_and_exit(&tag, hook_result);
-
-        let sign = self.repository.gpg_sign();
-        self.repository
-            .commit(&format!("chore(version): {tag}"), sign)?;
-
-        if let Some(msg_tmpl) = annotated {
-            let mut context = tera::Context::new();
-            context.insert("latest", &current_tag.version.to_string());
-            context.insert("version", &tag.version.to_string());
-            let msg = Tera::one_off(&msg_tmpl, &context, false)?;
-            self.repository.create_annotated_tag(&tag, &msg)?;
+        let hook_options = HookRunOptions::pre_bump()
+            .current_tag(current.as_ref())
+            .next_version(&next_version)
+            .hook_profile(hooks_config)
+            .package(package_name, package);
+
+        changelog.pretty_print_bump_summary(&SETTINGS.package_commit_types(package_name))?;
+        let hook_result = if !options.no_changelog {
+            changelog.write_to_file(path, template, additional_context)?;
+            self.run_hooks(hook_options.with_hook_type(HookType::PostChangelog))
         } else {
-            self.repository.create_tag(&tag)?;
+            Ok(())
+        };
+
+        let hook_result = hook_result.and_then(|()| self.run_hooks(hook_options));
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_run_failure_hooks(&tag, hook_result, hook_options);
+
+        self.commit_version(&commit_message, &options)?;
+
+        if options.create_tag() {
+            if let Some(msg_tmpl) = annotated {
+                let mut context = tera::Context::new();
+                context.insert("latest", &latest_tag.version.to_string());
+                context.insert("version", &tag.version.to_string());
+                let msg = Tera::one_off(&msg_tmpl, &context, false)?;
+                self.repository.create_annotated_tag(&tag, &msg)?;
+            } else {
+                self.repository.create_tag(&tag)?;
+            }
         }
 
         self.run_hooks(
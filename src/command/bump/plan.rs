@@ -0,0 +1,183 @@
+use crate::command::bump::HookRunOptions;
+use crate::conventional::changelog::release::Release;
+use crate::conventional::changelog::template::Template;
+use crate::conventional::changelog::ReleaseType;
+use crate::git::tag::Tag;
+use crate::CocoGitto;
+use anyhow::Result;
+use colored::*;
+use log::info;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct HookPlan {
+    stage: String,
+    command: String,
+    cwd: PathBuf,
+}
+
+/// Everything a bump would do, collected instead of being applied on `--dry-run`
+#[derive(Default)]
+pub(super) struct BumpPlan {
+    versions: Vec<(String, String)>,
+    changelogs: Vec<(PathBuf, String)>,
+    hooks: Vec<HookPlan>,
+    commit_message: Option<String>,
+    tags: Vec<Tag>,
+}
+
+impl BumpPlan {
+    pub(super) fn version(&mut self, current: Option<&Tag>, next: &Tag) {
+        let current = current
+            .map(|current| current.to_string())
+            .unwrap_or_else(|| "...".to_string());
+        self.versions.push((current, next.to_string()));
+    }
+
+    pub(super) fn changelog<S: AsRef<Path>>(
+        &mut self,
+        release: Release,
+        path: S,
+        template: Template,
+        kind: ReleaseType,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let previous = fs::read_to_string(path).unwrap_or_default();
+        let next = release.render_into_changelog(path, template, kind)?;
+        self.changelogs
+            .push((path.to_path_buf(), changelog_diff(&previous, &next)));
+        Ok(())
+    }
+
+    pub(super) fn commit(&mut self, message: &str) {
+        self.commit_message = Some(message.to_string());
+    }
+
+    pub(super) fn tag(&mut self, tag: &Tag) {
+        self.tags.push(tag.clone());
+    }
+
+    pub(super) fn print(&self) -> Result<()> {
+        let mut output = format!("{}\n", "Bump plan (dry-run)".underline().white().bold());
+
+        writeln!(output, "\n{}", "Versions:".bold())?;
+        for (current, next) in &self.versions {
+            writeln!(output, "  {current} -> {}", next.green())?;
+        }
+
+        for (path, diff) in &self.changelogs {
+            writeln!(output, "\n{} {}", "Changelog:".bold(), path.display())?;
+            output.push_str(diff);
+        }
+
+        writeln!(output, "\n{}", "Hooks:".bold())?;
+        if self.hooks.is_empty() {
+            writeln!(output, "  none")?;
+        }
+        for hook in &self.hooks {
+            writeln!(
+                output,
+                "  [{}] {} (in {})",
+                hook.stage,
+                hook.command,
+                hook.cwd.display()
+            )?;
+        }
+
+        if let Some(message) = &self.commit_message {
+            writeln!(output, "\n{} {message}", "Commit:".bold())?;
+        }
+
+        writeln!(output, "\n{}", "Tags:".bold())?;
+        for tag in &self.tags {
+            writeln!(output, "  {tag}")?;
+        }
+
+        info!("{output}");
+        Ok(())
+    }
+}
+
+impl CocoGitto {
+    /// Add the hooks that would run with the given options to the plan
+    pub(super) fn plan_hooks(&self, plan: &mut BumpPlan, options: HookRunOptions) -> Result<()> {
+        let package_path = options.package.map(|package| package.path.as_path());
+        let context = options.hook_context(&self.repository);
+
+        for hook in self.get_hooks(&options, &context)? {
+            if !hook.is_enabled(&context) {
+                continue;
+            }
+
+            plan.hooks.push(HookPlan {
+                stage: options.label(),
+                command: hook.to_string(),
+                cwd: hook
+                    .current_dir(package_path)
+                    .unwrap_or_else(|| PathBuf::from(".")),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Line diff between two changelog contents, only the changed region is displayed
+fn changelog_diff(previous: &str, next: &str) -> String {
+    let previous: Vec<&str> = previous.lines().collect();
+    let next: Vec<&str> = next.lines().collect();
+
+    let prefix = previous
+        .iter()
+        .zip(next.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(next[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let removed = &previous[prefix..previous.len() - suffix];
+    let added = &next[prefix..next.len() - suffix];
+
+    // Like unified diffs, an empty side points to the line preceding the change
+    let start = |len: usize| if len == 0 { prefix } else { prefix + 1 };
+    let mut diff = format!(
+        "@@ -{},{} +{},{} @@\n",
+        start(removed.len()),
+        removed.len(),
+        start(added.len()),
+        added.len()
+    );
+
+    for line in removed {
+        diff.push_str(&format!("-{line}\n").red().to_string());
+    }
+
+    for line in added {
+        diff.push_str(&format!("+{line}\n").green().to_string());
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod test {
+    use super::changelog_diff;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn changelog_diff_shows_inserted_lines() {
+        colored::control::set_override(false);
+        let previous = "# Changelog\n- - -\n## 1.0.0\n";
+        let next = "# Changelog\n- - -\n## 1.1.0\n- - -\n## 1.0.0\n";
+
+        let diff = changelog_diff(previous, next);
+
+        assert_that!(diff.as_str()).is_equal_to("@@ -2,0 +3,2 @@\n+## 1.1.0\n+- - -\n");
+    }
+}
@@ -1,16 +1,17 @@
+use crate::command::bump::plan::BumpPlan;
 use crate::command::bump::{
-    ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero, HookRunOptions,
+    ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero, BumpOptions, HookRunOptions,
 };
 
 use crate::conventional::changelog::ReleaseType;
 use crate::conventional::version::IncrementCommand;
 use crate::git::tag::Tag;
 use crate::hook::HookVersion;
+use crate::settings::HookType;
 use crate::{settings, CocoGitto, SETTINGS};
 use anyhow::Result;
 use colored::*;
 use log::info;
-use semver::Prerelease;
 use tera::Tera;
 
 impl CocoGitto {
@@ -21,64 +22,113 @@ impl CocoGitto {
         hooks_config: Option<&str>,
         annotated: Option<String>,
         dry_run: bool,
+        options: BumpOptions,
     ) -> Result<()> {
         self.pre_bump_checks()?;
 
-        let current_tag = self.repository.get_latest_tag();
-        let current_tag = tag_or_fallback_to_zero(current_tag)?;
-        let mut tag = current_tag.bump(increment, &self.repository)?;
+        let channel = self.release_channel();
+        let current_tag = self.get_bump_origin(None, channel.as_ref())?;
+        let current_tag = self.seed_bump_origin(current_tag, &options)?;
+        let mut tag = match self.bump_or_fallback(&current_tag, increment, &options)? {
+            Some(tag) => tag,
+            None => return Ok(()),
+        };
 
         ensure_tag_is_greater_than_previous(&current_tag, &tag)?;
-
-        if let Some(pre_release) = pre_release {
-            tag.version.pre = Prerelease::new(pre_release)?;
-        }
+        self.apply_release_channel(&mut tag, None, pre_release, channel.as_ref())?;
+        tag.version.build = self.get_build_metadata(&options)?;
 
         let tag = Tag::create(tag.version, None);
 
-        if dry_run {
-            print!("{tag}");
-            return Ok(());
-        }
-
-        let pattern = self.get_revspec_for_tag(&current_tag)?;
+        let latest_tag = if options.from.is_some() || options.previous_version.is_some() {
+            current_tag.clone()
+        } else {
+            tag_or_fallback_to_zero(self.repository.get_latest_tag())?
+        };
+        let pattern = self.get_revspec_for_tag(&latest_tag)?;
         let changelog = self.get_changelog_with_target_version(pattern, tag.clone())?;
-        changelog.pretty_print_bump_summary()?;
 
         let path = settings::changelog_path();
         let template = SETTINGS.get_changelog_template()?;
 
-        changelog.write_to_file(path, template, ReleaseType::Standard)?;
-
-        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+        let current = if options.previous_version.is_some() {
+            Some(HookVersion::new(current_tag.clone()))
+        } else {
+            self.repository.get_latest_tag().map(HookVersion::new).ok()
+        };
 
         let next_version = HookVersion::new(tag.clone());
+        let commit_message = format!("chore(version): {}", next_version.prefixed_tag);
 
-        let hook_result = self.run_hooks(
-            HookRunOptions::pre_bump()
-                .current_tag(current.as_ref())
-                .next_version(&next_version)
-                .hook_profile(hooks_config),
-        );
+        if dry_run {
+            let mut plan = BumpPlan::default();
+            plan.version(current.as_ref().map(|v| &v.prefixed_tag), &tag);
+            if !options.no_changelog {
+                plan.changelog(changelog, path, template, ReleaseType::Standard)?;
+                self.plan_hooks(
+                    &mut plan,
+                    HookRunOptions::pre_bump()
+                        .with_hook_type(HookType::PostChangelog)
+                        .current_tag(current.as_ref())
+                        .next_version(&next_version)
+                        .hook_profile(hooks_config),
+                )?;
+            }
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::pre_bump()
+                    .current_tag(current.as_ref())
+                    .next_version(&next_version)
+                    .hook_profile(hooks_config),
+            )?;
+            if !options.no_commit {
+                plan.commit(&commit_message);
+            }
+            if options.create_tag() {
+                plan.tag(&tag);
+            }
+            self.plan_hooks(
+                &mut plan,
+                HookRunOptions::post_bump()
+                    .current_tag(current.as_ref())
+                    .next_version(&next_version)
+                    .hook_profile(hooks_config),
+            )?;
+            plan.print()?;
+            print!("{tag}");
+            return Ok(());
+        }
 
-        self.repository.add_all()?;
-        self.unwrap_or_stash_and_exit(&tag, hook_result);
+        let hook_options = HookRunOptions::pre_bump()
+            .current_tag(current.as_ref())
+            .next_version(&next_version)
+            .hook_profile(hooks_config);
 
-        let sign = self.repository.gpg_sign();
+        changelog.pretty_print_bump_summary(&SETTINGS.commit_types())?;
+        let hook_result = if !options.no_changelog {
+            changelog.write_to_file(path, template, ReleaseType::Standard)?;
+            self.run_hooks(hook_options.with_hook_type(HookType::PostChangelog))
+        } else {
+            Ok(())
+        };
 
-        self.repository.commit(
-            &format!("chore(version): {}", next_version.prefixed_tag),
-            sign,
-        )?;
+        let hook_result = hook_result.and_then(|()| self.run_hooks(hook_options));
 
-        if let Some(msg_tmpl) = annotated {
-            let mut context = tera::Context::new();
-            context.insert("latest", &current_tag.version.to_string());
-            context.insert("version", &tag.version.to_string());
-            let msg = Tera::one_off(&msg_tmpl, &context, false)?;
-            self.repository.create_annotated_tag(&tag, &msg)?;
-        } else {
-            self.repository.create_tag(&tag)?;
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_run_failure_hooks(&tag, hook_result, hook_options);
+
+        self.commit_version(&commit_message, &options)?;
+
+        if options.create_tag() {
+            if let Some(msg_tmpl) = annotated {
+                let mut context = tera::Context::new();
+                context.insert("latest", &latest_tag.version.to_string());
+                context.insert("version", &tag.version.to_string());
+                let msg = Tera::one_off(&msg_tmpl, &context, false)?;
+                self.repository.create_annotated_tag(&tag, &msg)?;
+            } else {
+                self.repository.create_tag(&tag)?;
+            }
         }
 
         self.run_hooks(
@@ -1,5 +1,5 @@
 use crate::conventional::changelog::release::Release;
-use crate::conventional::changelog::template::Template;
+use crate::conventional::changelog::template::{PackageContext, Template};
 use crate::git::revspec::RevspecPattern;
 use crate::CocoGitto;
 use anyhow::anyhow;
@@ -13,7 +13,7 @@ impl CocoGitto {
         &self,
         pattern: RevspecPattern,
         with_child_releases: bool,
-    ) -> Result<Release> {
+    ) -> Result<Release<'_>> {
         if with_child_releases {
             self.repository
                 .get_release_range(pattern)
@@ -25,6 +25,47 @@ impl CocoGitto {
         }
     }
 
+    /// ## Get the changelog of a monorepo package between two oids
+    /// - `from` default value:latest package tag or else first commit
+    /// - `to` default value:`HEAD`
+    pub fn get_package_changelog(
+        &self,
+        pattern: RevspecPattern,
+        package: &str,
+    ) -> Result<Release<'_>> {
+        let commit_range = self.repository.get_package_commit_range(pattern, package)?;
+
+        Ok(Release::from(commit_range).with_package_commit_types(package))
+    }
+
+    pub fn get_package_changelog_at_tag(
+        &self,
+        tag: &str,
+        package: &str,
+        template: Template,
+    ) -> Result<String> {
+        let pattern = format!("..{tag}");
+        let pattern = RevspecPattern::from(pattern.as_str());
+        self.render_package_changelog(pattern, package, template)
+    }
+
+    pub fn render_package_changelog(
+        &self,
+        pattern: RevspecPattern,
+        package: &str,
+        template: Template,
+    ) -> Result<String> {
+        let changelog = self.get_package_changelog(pattern, package)?;
+        let context = PackageContext {
+            package_name: package,
+            bump_reason: None,
+        };
+
+        changelog
+            .into_package_markdown(template, context)
+            .map_err(|err| anyhow!(err))
+    }
+
     pub fn get_changelog_at_tag(&self, tag: &str, template: Template) -> Result<String> {
         let pattern = format!("..{tag}");
         let pattern = RevspecPattern::from(pattern.as_str());
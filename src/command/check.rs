@@ -8,12 +8,30 @@ use colored::*;
 use log::info;
 
 impl CocoGitto {
-    pub fn check(&self, check_from_latest_tag: bool, ignore_merge_commits: bool) -> Result<()> {
+    pub fn check(
+        &self,
+        check_from_latest_tag: bool,
+        ignore_merge_commits: bool,
+        package: Option<&str>,
+    ) -> Result<()> {
         let commit_range = if check_from_latest_tag {
-            self.repository
-                .get_commit_range(&RevspecPattern::default())?
+            match package {
+                Some(package) => self
+                    .repository
+                    .get_package_commit_range(RevspecPattern::default(), package)?,
+                None => self
+                    .repository
+                    .get_commit_range(&RevspecPattern::default())?,
+            }
         } else {
-            self.repository.all_commits()?
+            let mut commit_range = self.repository.all_commits()?;
+            if let Some(package) = package {
+                commit_range.commits = self
+                    .repository
+                    .filter_package_commits(commit_range.commits, package)?;
+            }
+
+            commit_range
         };
 
         let errors: Vec<_> = if ignore_merge_commits {
@@ -1,5 +1,6 @@
+use crate::command::bump::HookRunOptions;
 use crate::conventional::commit::Commit;
-use crate::CocoGitto;
+use crate::{CocoGitto, SETTINGS};
 use anyhow::Result;
 use conventional_commit_parser::commit::{CommitType, ConventionalCommit};
 use conventional_commit_parser::parse_footers;
@@ -26,6 +27,17 @@ impl CocoGitto {
             None => Vec::with_capacity(0),
         };
 
+        // Commits scoped to a monorepo package run the package pre-commit hooks
+        let hook_options = match scope
+            .as_ref()
+            .and_then(|scope| SETTINGS.packages.get_key_value(scope))
+        {
+            Some((package_name, package)) => {
+                HookRunOptions::pre_commit().package(package_name, package)
+            }
+            None => HookRunOptions::pre_commit(),
+        };
+
         let conventional_message = ConventionalCommit {
             commit_type,
             scope,
@@ -39,6 +51,8 @@ impl CocoGitto {
         // Validate the message
         conventional_commit_parser::parse(&conventional_message)?;
 
+        self.run_hooks(hook_options)?;
+
         // Git commit
         let sign = sign || self.repository.gpg_sign();
         let oid = self.repository.commit(&conventional_message, sign)?;
@@ -1,10 +1,14 @@
 use anyhow::bail;
 use anyhow::Result;
-use log::warn;
-use semver::Version;
+use log::{info, warn};
+use serde_json::json;
 
+use crate::command::bump::BumpOptions;
+use crate::conventional::version::IncrementCommand;
 use crate::git::error::TagError;
-use crate::CocoGitto;
+use crate::git::tag::Tag;
+use crate::settings::MonoRepoVersioning;
+use crate::{CocoGitto, SETTINGS};
 
 impl CocoGitto {
     pub fn get_latest_version(
@@ -13,7 +17,7 @@ impl CocoGitto {
         package: Option<String>,
     ) -> Result<()> {
         let fallback = match fallback {
-            Some(input) => match Version::parse(&input) {
+            Some(input) => match Tag::parse_version(&input, &input) {
                 Ok(version) => Some(version),
                 Err(err) => {
                     warn!("Invalid fallback: {}", input);
@@ -37,8 +41,91 @@ impl CocoGitto {
             Err(err) => bail!("{}", err),
         };
 
+        let current_version = Tag::format_version(&current_version);
         warn!("Current version:");
         print!("{current_version}");
         Ok(())
     }
+
+    /// Compute the version `cog bump --auto` would create, without touching the repository.
+    pub fn get_next_version(&self, package: Option<String>, json: bool) -> Result<()> {
+        let channel = self.release_channel();
+        // With fixed versioning every package is released with the global version
+        let bump_package = package
+            .as_deref()
+            .filter(|_| SETTINGS.monorepo.versioning == MonoRepoVersioning::Independent);
+        let current = self.get_bump_origin(bump_package, channel.as_ref())?;
+        let options = BumpOptions::default();
+
+        // Same increment selection and fallback as `cog bump --auto`
+        let increment = match bump_package {
+            Some(package) => IncrementCommand::AutoPackage(package.to_string()),
+            None if !SETTINGS.packages.is_empty()
+                && SETTINGS.monorepo.versioning == MonoRepoVersioning::Independent
+                && SETTINGS.generate_mono_repository_global_tag =>
+            {
+                self.monorepo_global_increment(channel.as_ref(), &options)?
+            }
+            None => IncrementCommand::Auto,
+        };
+
+        let mut next = match self.bump_or_fallback(&current, increment, &options)? {
+            Some(tag) => tag,
+            None => bail!("No commit found to bump current version"),
+        };
+        self.apply_release_channel(&mut next, bump_package, None, channel.as_ref())?;
+
+        let commits = current.commits_since(&self.repository, bump_package)?;
+        let increment_type = next.get_increment_from(&current);
+
+        let current_version = if current.is_zero() {
+            None
+        } else {
+            Some(Tag::format_version(&current.version))
+        };
+        let next_version = Tag::format_version(&next.version);
+
+        if json {
+            let commits: Vec<_> = commits
+                .iter()
+                .map(|commit| {
+                    json!({
+                        "oid": commit.oid,
+                        "type": commit.message.commit_type.as_ref(),
+                        "scope": commit.message.scope,
+                        "summary": commit.message.summary,
+                        "breaking_change": commit.message.is_breaking_change,
+                    })
+                })
+                .collect();
+
+            let output = json!({
+                "package": package,
+                "current": current_version,
+                "next": next_version,
+                "increment": increment_type.map(|increment| increment.to_string()),
+                "commits": commits,
+            });
+
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
+        let current_version = current_version.unwrap_or_else(|| "...".to_string());
+        match increment_type {
+            Some(increment) => info!("Next version ({increment} bump from {current_version}):"),
+            None => info!("Next version (from {current_version}):"),
+        }
+        for commit in &commits {
+            info!(
+                "\t{} {}: {}",
+                commit.shorthand(),
+                commit.message.commit_type.as_ref(),
+                commit.message.summary
+            );
+        }
+
+        print!("{next_version}");
+        Ok(())
+    }
 }
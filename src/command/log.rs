@@ -5,8 +5,14 @@ use anyhow::Result;
 use std::fmt::Write;
 
 impl CocoGitto {
-    pub fn get_log(&self, filters: CommitFilters) -> Result<String> {
-        let commits = self.repository.all_commits()?;
+    pub fn get_log(&self, filters: CommitFilters, package: Option<&str>) -> Result<String> {
+        let mut commits = self.repository.all_commits()?;
+        if let Some(package) = package {
+            commits.commits = self
+                .repository
+                .filter_package_commits(commits.commits, package)?;
+        }
+
         let logs = commits
             .commits
             .iter()
@@ -31,7 +37,7 @@ impl CocoGitto {
     }
 
     pub fn get_repo_tag_name(&self) -> Option<String> {
-        let repo_path = self.repository.get_repo_dir()?.iter().last()?;
+        let repo_path = self.repository.get_repo_dir()?.iter().next_back()?;
         let mut repo_tag_name = repo_path.to_str()?.to_string();
 
         if let Some(branch_shorthand) = self.repository.get_branch_shorthand() {
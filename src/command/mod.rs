@@ -6,3 +6,5 @@ pub mod edit;
 pub mod get_version;
 pub mod init;
 pub mod log;
+pub mod packages;
+pub mod tags;
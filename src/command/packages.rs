@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use colored::*;
+use log::info;
+use serde::Serialize;
+
+use crate::command::bump::BumpOptions;
+use crate::conventional::error::BumpError;
+use crate::conventional::version::Increment;
+use crate::git::tag::Tag;
+use crate::{CocoGitto, SETTINGS};
+
+/// A package changed since its latest release, as listed by `cog packages changed`
+#[derive(Debug, Serialize)]
+struct ChangedPackage {
+    package: String,
+    path: PathBuf,
+    current: Option<String>,
+    next: Option<String>,
+    increment: Option<Increment>,
+    commits: usize,
+}
+
+impl CocoGitto {
+    /// List the packages with commits since their latest release, or since `since`,
+    /// along with the version the next auto bump would create. The repository is left untouched.
+    pub fn changed_packages(&self, since: Option<String>, json: bool) -> Result<()> {
+        let channel = self.release_channel();
+        let options = BumpOptions {
+            from: since,
+            ..Default::default()
+        };
+
+        let mut package_names: Vec<&String> = SETTINGS.packages.keys().collect();
+        package_names.sort();
+
+        let mut changed = vec![];
+        for package_name in package_names {
+            let package = &SETTINGS.packages[package_name];
+            let current = self.get_bump_origin(Some(package_name), channel.as_ref())?;
+            let current = self.seed_bump_origin(current, &options)?;
+
+            let commits = current.commits_since(&self.repository, Some(package_name))?;
+            if commits.is_empty() {
+                continue;
+            }
+
+            // Packages with only non bumping commits changed but have no next version
+            let increment = match current
+                .package_version_increment_from_commit_history(&commits, package_name)
+            {
+                Ok(increment) => Some(increment),
+                Err(BumpError::NoCommitFound) => None,
+                Err(err) => return Err(err.into()),
+            };
+
+            let next = match increment {
+                Some(increment) => {
+                    let mut next = current.bump(increment.into(), &self.repository)?;
+                    self.apply_release_channel(
+                        &mut next,
+                        Some(package_name),
+                        None,
+                        channel.as_ref(),
+                    )?;
+                    Some(Tag::format_version(&next.version))
+                }
+                None => None,
+            };
+
+            let current_version = if current.is_zero() {
+                None
+            } else {
+                Some(Tag::format_version(&current.version))
+            };
+
+            changed.push(ChangedPackage {
+                package: package_name.to_string(),
+                path: package.path.clone(),
+                current: current_version,
+                next,
+                increment,
+                commits: commits.len(),
+            });
+        }
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&changed)?);
+            return Ok(());
+        }
+
+        for package in &changed {
+            let current = package.current.as_deref().unwrap_or("...");
+            let next = package.next.as_deref().unwrap_or("no bump");
+            info!("{} {} -> {}", package.package.bold(), current, next.green());
+        }
+
+        let names: Vec<&str> = changed
+            .iter()
+            .map(|package| package.package.as_str())
+            .collect();
+        print!("{}", names.join("\n"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use colored::*;
+use log::info;
+
+use crate::CocoGitto;
+
+impl CocoGitto {
+    /// Create an alias with the current `tag_prefix` for each tag using one of the
+    /// `legacy_tag_prefixes`, so they can be dropped from the configuration afterward.
+    pub fn migrate_tags(&self, dry_run: bool) -> Result<()> {
+        let existing = self.repository.tags()?;
+        let mut created = 0;
+
+        for raw in &existing {
+            let tag = match self.repository.resolve_tag(raw) {
+                Ok(tag) => tag,
+                Err(_) => continue,
+            };
+
+            let alias = tag.to_string();
+            if alias == *raw || existing.contains(&alias) {
+                continue;
+            }
+
+            if !dry_run {
+                self.repository
+                    .create_tag_at(&alias, *tag.oid_unchecked())?;
+            }
+
+            info!("{} -> {}", raw, alias.green());
+            created += 1;
+        }
+
+        if dry_run {
+            info!("{created} tag(s) to create");
+        } else {
+            info!("Created {created} tag(s)");
+        }
+
+        Ok(())
+    }
+}
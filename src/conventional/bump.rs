@@ -1,10 +1,15 @@
+use crate::conventional::commit::commit_type_increment;
 use crate::conventional::error::BumpError;
-use crate::conventional::version::Increment;
-use crate::{Commit, IncrementCommand, Repository, RevspecPattern, Tag, SETTINGS};
-use conventional_commit_parser::commit::CommitType;
-use git2::Commit as Git2Commit;
+use crate::conventional::version::{Increment, VersionScheme};
+use crate::git::error::TagError;
+use crate::{
+    Commit, CommitsMetadata, IncrementCommand, Repository, RevspecPattern, Tag, COMMITS_METADATA,
+    SETTINGS,
+};
+use chrono::Utc;
+use git2::{Commit as Git2Commit, Oid};
 use once_cell::sync::Lazy;
-use semver::{BuildMetadata, Prerelease, Version};
+use semver::{BuildMetadata, Prerelease};
 
 static FILTER_MERGE_COMMITS: Lazy<fn(&&git2::Commit) -> bool> = Lazy::new(|| {
     |commit| {
@@ -17,7 +22,7 @@ static FILTER_MERGE_COMMITS: Lazy<fn(&&git2::Commit) -> bool> = Lazy::new(|| {
 });
 
 pub(crate) trait Bump {
-    fn manual_bump(&self, version: &str) -> Result<Self, semver::Error>
+    fn manual_bump(&self, version: &str) -> Result<Self, TagError>
     where
         Self: Sized;
     fn major_bump(&self) -> Self;
@@ -39,9 +44,9 @@ pub(crate) trait Bump {
 }
 
 impl Bump for Tag {
-    fn manual_bump(&self, version: &str) -> Result<Self, semver::Error> {
+    fn manual_bump(&self, version: &str) -> Result<Self, TagError> {
         let mut next = self.clone();
-        next.version = Version::parse(version)?;
+        next.version = Tag::parse_version(version, version)?;
         Ok(next)
     }
 
@@ -108,9 +113,9 @@ impl Tag {
         repository: &Repository,
     ) -> Result<Self, BumpError> {
         match increment {
-            IncrementCommand::Major => Ok(self.major_bump()),
-            IncrementCommand::Minor => Ok(self.minor_bump()),
-            IncrementCommand::Patch => Ok(self.patch_bump()),
+            IncrementCommand::Major => Ok(self.increment(Increment::Major)),
+            IncrementCommand::Minor => Ok(self.increment(Increment::Minor)),
+            IncrementCommand::Patch => Ok(self.increment(Increment::Patch)),
             IncrementCommand::Auto => self.auto_bump(repository),
             IncrementCommand::AutoPackage(package) => self.auto_package_bump(repository, &package),
             IncrementCommand::AutoMonoRepoGlobal(package_increment) => {
@@ -120,6 +125,26 @@ impl Tag {
         }
     }
 
+    /// Apply the given increment according to the configured version scheme.
+    /// With CalVer, any increment yields the next calendar version.
+    fn increment(&self, increment: Increment) -> Self {
+        match SETTINGS.version_scheme {
+            VersionScheme::Semver => match increment {
+                Increment::Major => self.major_bump(),
+                Increment::Minor => self.minor_bump(),
+                Increment::Patch => self.patch_bump(),
+            },
+            VersionScheme::Calver => self.calver_bump(),
+        }
+    }
+
+    fn calver_bump(&self) -> Self {
+        let mut next = self.clone();
+        let today = Utc::now().date_naive();
+        next.version = SETTINGS.calver_format.next(&self.version, today);
+        next.reset_metadata()
+    }
+
     fn reset_metadata(mut self) -> Self {
         self.version.build = BuildMetadata::EMPTY;
         self.version.pre = Prerelease::EMPTY;
@@ -127,40 +152,51 @@ impl Tag {
         self
     }
 
-    fn get_version_from_commit_history(&self, repository: &Repository) -> Result<Tag, BumpError> {
-        let changelog_start_oid = repository
-            .get_latest_tag_oid()
-            .ok()
-            .unwrap_or_else(|| repository.get_first_commit().expect("non empty repository"));
-        let changelog_start_oid = changelog_start_oid.to_string();
-        let changelog_start_oid = Some(changelog_start_oid.as_str());
+    /// The commit the history analysis starts from: this tag when it points to a commit,
+    /// the given latest tag otherwise, or the first commit if there is no previous version.
+    fn history_start(&self, repository: &Repository, latest: Option<Oid>) -> Oid {
+        self.oid
+            .or_else(|| if self.is_zero() { None } else { latest })
+            .unwrap_or_else(|| repository.get_first_commit().expect("non empty repository"))
+    }
 
-        let pattern = changelog_start_oid
-            .map(|oid| format!("{oid}.."))
-            .unwrap_or_else(|| "..".to_string());
-        let pattern = pattern.as_str();
-        let pattern = RevspecPattern::from(pattern);
-        let commits = repository.get_commit_range(&pattern)?;
+    /// Get the conventional commits an auto bump from this tag is computed from, only
+    /// considering commits touching the given package if any.
+    pub(crate) fn commits_since(
+        &self,
+        repository: &Repository,
+        package: Option<&str>,
+    ) -> Result<Vec<Commit>, BumpError> {
+        let latest = match package {
+            Some(package) => repository
+                .get_latest_package_tag(package)
+                .ok()
+                .and_then(|tag| tag.oid),
+            None => repository.get_latest_tag_oid().ok(),
+        };
 
-        let commits: Vec<&Git2Commit> = commits
+        let changelog_start_oid = self.history_start(repository, latest);
+        let pattern = format!("{changelog_start_oid}..");
+        let pattern = RevspecPattern::from(pattern.as_str());
+        let commits = match package {
+            Some(package) => repository.get_commit_range_for_package(&pattern, package)?,
+            None => repository.get_commit_range(&pattern)?,
+        };
+
+        Ok(commits
             .commits
             .iter()
             .filter(&*FILTER_MERGE_COMMITS)
-            .collect();
-
-        let conventional_commits: Vec<Commit> = commits
-            .iter()
-            .map(|commit| Commit::from_git_commit(commit))
+            .map(Commit::from_git_commit)
             .filter_map(Result::ok)
-            .collect();
+            .collect())
+    }
 
+    fn get_version_from_commit_history(&self, repository: &Repository) -> Result<Tag, BumpError> {
+        let conventional_commits = self.commits_since(repository, None)?;
         let increment_type = self.version_increment_from_commit_history(&conventional_commits)?;
 
-        Ok(match increment_type {
-            Increment::Major => self.major_bump(),
-            Increment::Minor => self.minor_bump(),
-            Increment::Patch => self.patch_bump(),
-        })
+        Ok(self.increment(increment_type))
     }
 
     fn get_package_version_from_commit_history(
@@ -168,50 +204,19 @@ impl Tag {
         package: &str,
         repository: &Repository,
     ) -> Result<Tag, BumpError> {
-        let changelog_start_oid = repository
-            .get_latest_package_tag(package)
-            .ok()
-            .and_then(|tag| tag.oid)
-            .unwrap_or_else(|| repository.get_first_commit().expect("non empty repository"));
-
-        let changelog_start_oid = changelog_start_oid.to_string();
-        let changelog_start_oid = Some(changelog_start_oid.as_str());
+        let conventional_commits = self.commits_since(repository, Some(package))?;
+        let increment_type =
+            self.package_version_increment_from_commit_history(&conventional_commits, package)?;
 
-        let pattern = changelog_start_oid
-            .map(|oid| format!("{oid}.."))
-            .unwrap_or_else(|| "..".to_string());
-        let pattern = pattern.as_str();
-        let pattern = RevspecPattern::from(pattern);
-        let commits = repository.get_commit_range_for_package(&pattern, package)?;
-        let commits: Vec<&Git2Commit> = commits
-            .commits
-            .iter()
-            .filter(&*FILTER_MERGE_COMMITS)
-            .collect();
-
-        let conventional_commits: Vec<Commit> = commits
-            .iter()
-            .map(|commit| Commit::from_git_commit(commit))
-            .filter_map(Result::ok)
-            .collect();
-
-        let increment_type = self.version_increment_from_commit_history(&conventional_commits)?;
-
-        Ok(match increment_type {
-            Increment::Major => self.major_bump(),
-            Increment::Minor => self.minor_bump(),
-            Increment::Patch => self.patch_bump(),
-        })
+        Ok(self.increment(increment_type))
     }
 
     fn get_monorepo_global_version_from_commit_history(
         &self,
         repository: &Repository,
     ) -> Result<Tag, BumpError> {
-        let changelog_start_oid = repository
-            .get_latest_tag_oid()
-            .ok()
-            .unwrap_or_else(|| repository.get_first_commit().expect("non empty repository"));
+        let changelog_start_oid =
+            self.history_start(repository, repository.get_latest_tag_oid().ok());
 
         let changelog_start_oid = changelog_start_oid.to_string();
         let changelog_start_oid = Some(changelog_start_oid.as_str());
@@ -237,41 +242,48 @@ impl Tag {
 
         let increment_type = self.version_increment_from_commit_history(&conventional_commits)?;
 
-        Ok(match increment_type {
-            Increment::Major => self.major_bump(),
-            Increment::Minor => self.minor_bump(),
-            Increment::Patch => self.patch_bump(),
-        })
+        Ok(self.increment(increment_type))
     }
 
     pub fn version_increment_from_commit_history(
         &self,
         commits: &[Commit],
     ) -> Result<Increment, BumpError> {
-        let is_major_bump = || {
-            self.version.major != 0
-                && commits
-                    .iter()
-                    .any(|commit| commit.message.is_breaking_change)
-        };
+        self.increment_from_commits(commits, &COMMITS_METADATA)
+    }
 
-        let is_minor_bump = || {
-            commits
-                .iter()
-                .any(|commit| commit.message.commit_type == CommitType::Feature)
-        };
+    /// Same as [`Tag::version_increment_from_commit_history`], using the
+    /// package `commit_types` bump rules
+    pub fn package_version_increment_from_commit_history(
+        &self,
+        commits: &[Commit],
+        package: &str,
+    ) -> Result<Increment, BumpError> {
+        self.increment_from_commits(commits, &SETTINGS.package_commit_types(package))
+    }
 
-        let is_patch_bump = || {
-            commits
-                .iter()
-                .any(|commit| commit.message.commit_type == CommitType::BugFix)
-        };
+    fn increment_from_commits(
+        &self,
+        commits: &[Commit],
+        commit_types: &CommitsMetadata,
+    ) -> Result<Increment, BumpError> {
+        let increments: Vec<Increment> = commits
+            .iter()
+            .filter_map(|commit| {
+                let commit_type = &commit.message.commit_type;
+                if self.version.major != 0 && commit.message.is_breaking_change {
+                    return Some(Increment::Major);
+                }
+
+                commit_type_increment(commit_types, commit_type)
+            })
+            .collect();
 
-        if is_major_bump() {
+        if increments.contains(&Increment::Major) {
             Ok(Increment::Major)
-        } else if is_minor_bump() {
+        } else if increments.contains(&Increment::Minor) {
             Ok(Increment::Minor)
-        } else if is_patch_bump() {
+        } else if increments.contains(&Increment::Patch) {
             Ok(Increment::Patch)
         } else {
             Err(BumpError::NoCommitFound)
@@ -34,12 +34,35 @@ impl Release<'_> {
         renderer.render(self)
     }
 
+    pub fn into_package_markdown(
+        self,
+        template: Template,
+        context: PackageContext,
+    ) -> Result<String, tera::Error> {
+        let renderer = Renderer::try_new(template)?;
+        let mut renderer = renderer.with_package_context(context);
+        renderer.render(self)
+    }
+
     pub fn write_to_file<S: AsRef<Path>>(
         self,
         path: S,
         template: Template,
         kind: ReleaseType,
     ) -> Result<(), ChangelogError> {
+        let changelog_content = self.render_into_changelog(path.as_ref(), template, kind)?;
+        fs::write(path.as_ref(), changelog_content)?;
+        Ok(())
+    }
+
+    /// Render the release and insert it into the changelog at `path`,
+    /// returning the resulting content without writing it.
+    pub fn render_into_changelog<S: AsRef<Path>>(
+        self,
+        path: S,
+        template: Template,
+        kind: ReleaseType,
+    ) -> Result<String, ChangelogError> {
         let renderer = Renderer::try_new(template)?;
 
         let mut renderer = match kind {
@@ -62,9 +85,8 @@ impl Release<'_> {
                 idx + CHANGELOG_SEPARATOR.len() + 1 + changelog.len(),
                 "\n- - -\n",
             );
-            fs::write(path.as_ref(), changelog_content)?;
 
-            Ok(())
+            Ok(changelog_content)
         } else {
             Err(ChangelogError::SeparatorNotFound(
                 path.as_ref().to_path_buf(),
@@ -47,6 +47,18 @@ impl Release<'_> {
             .iter()
             .any(|commit| commit.commit.oid == oid.to_string())
     }
+
+    /// Use the package `commit_types` changelog titles
+    pub(crate) fn with_package_commit_types(mut self, package: &str) -> Self {
+        let commit_types = SETTINGS.package_commit_types(package);
+        for commit in &mut self.commits {
+            commit.changelog_title = commit_types
+                .get(&commit.commit.message.commit_type)
+                .map(|config| config.changelog_title.clone());
+        }
+
+        self
+    }
 }
 
 impl<'a> From<CommitRange<'a>> for Release<'a> {
@@ -81,6 +93,8 @@ impl<'a> From<CommitRange<'a>> for Release<'a> {
 #[derive(Debug)]
 pub struct ChangelogCommit<'a> {
     pub author_username: Option<&'a str>,
+    /// Overrides the global changelog title of the commit type
+    pub changelog_title: Option<String>,
     pub commit: Commit,
 }
 
@@ -90,6 +104,7 @@ impl From<Commit> for ChangelogCommit<'_> {
 
         ChangelogCommit {
             author_username,
+            changelog_title: None,
             commit,
         }
     }
@@ -555,6 +570,7 @@ mod test {
                 commits: vec![
                     ChangelogCommit {
                         author_username: Some("oknozor"),
+                        changelog_title: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
                             message: ConventionalCommit {
@@ -575,6 +591,7 @@ mod test {
                     },
                     ChangelogCommit {
                         author_username: None,
+                        changelog_title: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
                             message: ConventionalCommit {
@@ -595,6 +612,7 @@ mod test {
                     },
                     ChangelogCommit {
                         author_username: Some("oknozor"),
+                        changelog_title: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
                             message: ConventionalCommit {
@@ -684,6 +702,7 @@ mod test {
     fn package_renderer(renderer: Renderer) -> Result<Renderer> {
         let renderer = renderer.with_package_context(PackageContext {
             package_name: "one",
+            bump_reason: None,
         });
 
         Ok(renderer)
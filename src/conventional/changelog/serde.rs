@@ -30,11 +30,13 @@ impl Serialize for ChangelogCommit<'_> {
             .map(ChangelogFooter::from)
             .collect::<Vec<ChangelogFooter>>();
 
-        let commit_type = &COMMITS_METADATA
-            .iter()
-            .find(|(commit_type, _config)| *commit_type == &self.commit.message.commit_type)
-            .map(|meta| meta.1.changelog_title.clone())
-            .unwrap_or_else(|| self.commit.message.commit_type.to_string());
+        let commit_type = &self.changelog_title.clone().unwrap_or_else(|| {
+            COMMITS_METADATA
+                .iter()
+                .find(|(commit_type, _config)| *commit_type == &self.commit.message.commit_type)
+                .map(|meta| meta.1.changelog_title.clone())
+                .unwrap_or_else(|| self.commit.message.commit_type.to_string())
+        });
 
         commit.serialize_field("id", &self.commit.oid)?;
         commit.serialize_field("author", &self.author_username)?;
@@ -97,6 +99,7 @@ mod test {
     fn should_serialize_commit() {
         let commit = ChangelogCommit {
             author_username: Some("Jm Doudou"),
+            changelog_title: None,
             commit: Commit {
                 oid: "1234567890".to_string(),
                 message: ConventionalCommit {
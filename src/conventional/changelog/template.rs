@@ -45,8 +45,9 @@ impl Template {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum TemplateKind {
+    #[default]
     Default,
     FullHash,
     Remote,
@@ -59,12 +60,6 @@ pub enum TemplateKind {
     Custom(PathBuf),
 }
 
-impl Default for TemplateKind {
-    fn default() -> Self {
-        TemplateKind::Default
-    }
-}
-
 impl TemplateKind {
     /// Returns either a predefined template or a custom template
     fn from_arg(value: &str) -> Result<Self, ChangelogError> {
@@ -145,6 +140,9 @@ pub struct PackageBumpContext<'a> {
 #[derive(Debug)]
 pub struct PackageContext<'a> {
     pub package_name: &'a str,
+    /// Why the package was bumped when it is not only because of its own commits,
+    /// ex: "dependency core bumped"
+    pub bump_reason: Option<&'a str>,
 }
 
 pub(crate) trait ToContext {
@@ -164,6 +162,7 @@ impl<'a> ToContext for PackageContext<'a> {
     fn to_context(&self) -> Context {
         let mut context = tera::Context::new();
         context.insert("package_name", &self.package_name);
+        context.insert("bump_reason", &self.bump_reason);
         context
     }
 }
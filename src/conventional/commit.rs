@@ -2,10 +2,11 @@ use std::cmp::Ordering;
 use std::fmt::{self, Formatter};
 
 use crate::conventional::error::ConventionalCommitError;
-use crate::SETTINGS;
+use crate::conventional::version::Increment;
+use crate::{CommitsMetadata, SETTINGS};
 use chrono::{NaiveDateTime, Utc};
 use colored::*;
-use conventional_commit_parser::commit::ConventionalCommit;
+use conventional_commit_parser::commit::{CommitType, ConventionalCommit};
 use git2::Commit as Git2Commit;
 use log::info;
 use serde::{Deserialize, Serialize};
@@ -21,12 +22,54 @@ pub struct Commit {
 #[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
 pub struct CommitConfig {
     pub changelog_title: String,
+    /// Increment triggered by commits of this type, `feat` and `fix` bump
+    /// the minor and patch version when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bump: Option<CommitBump>,
+}
+
+/// Bump rule of a commit type, `none` disables the default `feat` and `fix` bumps
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitBump {
+    Major,
+    Minor,
+    Patch,
+    None,
+}
+
+impl From<CommitBump> for Option<Increment> {
+    fn from(bump: CommitBump) -> Self {
+        match bump {
+            CommitBump::Major => Some(Increment::Major),
+            CommitBump::Minor => Some(Increment::Minor),
+            CommitBump::Patch => Some(Increment::Patch),
+            CommitBump::None => None,
+        }
+    }
+}
+
+/// The increment commits of the given type trigger according to the configured bump
+/// rules, `feat` and `fix` commits bump the minor and patch version when no rule is set
+pub(crate) fn commit_type_increment(
+    commit_types: &CommitsMetadata,
+    commit_type: &CommitType,
+) -> Option<Increment> {
+    match commit_types.get(commit_type).and_then(|config| config.bump) {
+        Some(bump) => bump.into(),
+        None => match commit_type {
+            CommitType::Feature => Some(Increment::Minor),
+            CommitType::BugFix => Some(Increment::Patch),
+            _ => None,
+        },
+    }
 }
 
 impl CommitConfig {
     pub(crate) fn new(changelog_title: &str) -> Self {
         CommitConfig {
             changelog_title: changelog_title.to_string(),
+            bump: None,
         }
     }
 }
@@ -56,14 +99,15 @@ impl Commit {
                     date,
                 };
 
-                match &SETTINGS.commit_types().get(&commit.message.commit_type) {
-                    Some(_) => Ok(commit),
-                    None => Err(Box::new(ConventionalCommitError::CommitTypeNotAllowed {
+                if SETTINGS.is_commit_type_allowed(&commit.message.commit_type) {
+                    Ok(commit)
+                } else {
+                    Err(Box::new(ConventionalCommitError::CommitTypeNotAllowed {
                         oid: commit.oid.to_string(),
                         summary: format_summary(&commit.message),
                         commit_type: commit.message.commit_type.to_string(),
                         author: commit.author,
-                    })),
+                    }))
                 }
             }
             Err(cause) => {
@@ -177,7 +221,7 @@ impl fmt::Display for Commit {
 
 impl PartialOrd for Commit {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.message.scope.partial_cmp(&other.message.scope)
+        Some(self.cmp(other))
     }
 }
 
@@ -209,26 +253,24 @@ pub fn verify(
     let commit = conventional_commit_parser::parse(msg);
 
     match commit {
-        Ok(commit) => match &SETTINGS.commit_types().get(&commit.commit_type) {
-            Some(_) => {
-                info!(
-                    "{}",
-                    Commit {
-                        oid: "not committed".to_string(),
-                        message: commit,
-                        date: Utc::now().naive_utc(),
-                        author: author.unwrap_or_else(|| "Unknown".to_string()),
-                    }
-                );
-                Ok(())
-            }
-            None => Err(Box::new(ConventionalCommitError::CommitTypeNotAllowed {
-                oid: "not committed".to_string(),
-                summary: format_summary(&commit),
-                commit_type: commit.commit_type.to_string(),
-                author: author.unwrap_or_else(|| "Unknown".to_string()),
-            })),
-        },
+        Ok(commit) if SETTINGS.is_commit_type_allowed(&commit.commit_type) => {
+            info!(
+                "{}",
+                Commit {
+                    oid: "not committed".to_string(),
+                    message: commit,
+                    date: Utc::now().naive_utc(),
+                    author: author.unwrap_or_else(|| "Unknown".to_string()),
+                }
+            );
+            Ok(())
+        }
+        Ok(commit) => Err(Box::new(ConventionalCommitError::CommitTypeNotAllowed {
+            oid: "not committed".to_string(),
+            summary: format_summary(&commit),
+            commit_type: commit.commit_type.to_string(),
+            author: author.unwrap_or_else(|| "Unknown".to_string()),
+        })),
         Err(err) => Err(Box::new(ConventionalCommitError::ParseError(err))),
     }
 }
@@ -312,7 +354,7 @@ mod test {
             },
         ]);
 
-        assert_that!(commit.to_string()).is_equal_to(&message.to_string())
+        assert_that!(commit.to_string()).is_equal_to(message.to_string())
     }
 
     #[test]
@@ -1,4 +1,10 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Formatter;
+
+use chrono::{Datelike, NaiveDate};
+use semver::{BuildMetadata, Prerelease, Version};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum IncrementCommand {
@@ -11,7 +17,8 @@ pub enum IncrementCommand {
     Manual(String),
 }
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Increment {
     Major,
     Minor,
@@ -28,22 +35,287 @@ impl From<Increment> for IncrementCommand {
     }
 }
 
+impl fmt::Display for Increment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Increment::Major => write!(f, "major"),
+            Increment::Minor => write!(f, "minor"),
+            Increment::Patch => write!(f, "patch"),
+        }
+    }
+}
+
 impl Ord for Increment {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        match (self, other) {
+            (increment, other) if increment == other => Ordering::Equal,
+            (Increment::Major, _) => Ordering::Greater,
+            (_, Increment::Major) => Ordering::Less,
+            (Increment::Minor, _) => Ordering::Greater,
+            (_, Increment::Minor) => Ordering::Less,
+            (Increment::Patch, Increment::Patch) => Ordering::Equal,
+        }
     }
 }
 
 impl PartialOrd for Increment {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match (self, other) {
-            (increment, other) if increment == other => Some(Ordering::Equal),
-            (Increment::Major, _) => Some(Ordering::Greater),
-            (_, Increment::Major) => Some(Ordering::Less),
-            (Increment::Minor, _) => Some(Ordering::Greater),
-            (_, Increment::Minor) => Some(Ordering::Less),
-            (Increment::Patch, Increment::Patch) => Some(Ordering::Equal),
+        Some(self.cmp(other))
+    }
+}
+
+/// The versioning scheme used to parse, display and increment tags.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionScheme {
+    /// Semantic versioning, see <https://semver.org>
+    #[default]
+    Semver,
+    /// Calendar versioning using the configured `calver_format`, see <https://calver.org>
+    Calver,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum CalVerSegment {
+    FullYear,
+    ShortYear,
+    PaddedYear,
+    Month,
+    PaddedMonth,
+    Week,
+    PaddedWeek,
+    Day,
+    PaddedDay,
+    Micro,
+}
+
+impl CalVerSegment {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "YYYY" => Some(CalVerSegment::FullYear),
+            "YY" => Some(CalVerSegment::ShortYear),
+            "0Y" => Some(CalVerSegment::PaddedYear),
+            "MM" => Some(CalVerSegment::Month),
+            "0M" => Some(CalVerSegment::PaddedMonth),
+            "WW" => Some(CalVerSegment::Week),
+            "0W" => Some(CalVerSegment::PaddedWeek),
+            "DD" => Some(CalVerSegment::Day),
+            "0D" => Some(CalVerSegment::PaddedDay),
+            "MICRO" => Some(CalVerSegment::Micro),
+            _ => None,
+        }
+    }
+
+    fn token(&self) -> &'static str {
+        match self {
+            CalVerSegment::FullYear => "YYYY",
+            CalVerSegment::ShortYear => "YY",
+            CalVerSegment::PaddedYear => "0Y",
+            CalVerSegment::Month => "MM",
+            CalVerSegment::PaddedMonth => "0M",
+            CalVerSegment::Week => "WW",
+            CalVerSegment::PaddedWeek => "0W",
+            CalVerSegment::Day => "DD",
+            CalVerSegment::PaddedDay => "0D",
+            CalVerSegment::Micro => "MICRO",
+        }
+    }
+
+    fn is_padded(&self) -> bool {
+        matches!(
+            self,
+            CalVerSegment::PaddedYear
+                | CalVerSegment::PaddedMonth
+                | CalVerSegment::PaddedWeek
+                | CalVerSegment::PaddedDay
+        )
+    }
+
+    fn is_valid(&self, value: u64) -> bool {
+        match self {
+            CalVerSegment::Month | CalVerSegment::PaddedMonth => (1..=12).contains(&value),
+            CalVerSegment::Week | CalVerSegment::PaddedWeek => (1..=53).contains(&value),
+            CalVerSegment::Day | CalVerSegment::PaddedDay => (1..=31).contains(&value),
+            _ => true,
+        }
+    }
+
+    fn is_week(&self) -> bool {
+        matches!(self, CalVerSegment::Week | CalVerSegment::PaddedWeek)
+    }
+
+    // With `week_based`, years are ISO week years so that versions never go backward
+    // around new year, ex: 2024-12-30 is in week 1 of 2025
+    fn value_for(&self, date: NaiveDate, week_based: bool) -> u64 {
+        let year = if week_based {
+            date.iso_week().year()
+        } else {
+            date.year()
+        };
+
+        match self {
+            CalVerSegment::FullYear => year as u64,
+            CalVerSegment::ShortYear | CalVerSegment::PaddedYear => (year - 2000) as u64,
+            CalVerSegment::Month | CalVerSegment::PaddedMonth => date.month() as u64,
+            CalVerSegment::Week | CalVerSegment::PaddedWeek => date.iso_week().week() as u64,
+            CalVerSegment::Day | CalVerSegment::PaddedDay => date.day() as u64,
+            CalVerSegment::Micro => 0,
+        }
+    }
+}
+
+/// A CalVer format string such as `YYYY.0M.MICRO`.
+///
+/// Segments are mapped in order onto the semver major, minor and patch
+/// numbers, so a format holds at most three segments and must end with `MICRO`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CalVerFormat(Vec<CalVerSegment>);
+
+impl Default for CalVerFormat {
+    fn default() -> Self {
+        CalVerFormat(vec![
+            CalVerSegment::FullYear,
+            CalVerSegment::Month,
+            CalVerSegment::Micro,
+        ])
+    }
+}
+
+impl TryFrom<String> for CalVerFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let segments = value
+            .split('.')
+            .map(|token| {
+                CalVerSegment::from_token(token)
+                    .ok_or_else(|| format!("unknown CalVer token `{token}` in `{value}`"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let micro_count = segments
+            .iter()
+            .filter(|segment| **segment == CalVerSegment::Micro)
+            .count();
+
+        if segments.len() < 2 || segments.len() > 3 {
+            return Err(format!(
+                "CalVer format `{value}` must contain two or three segments"
+            ));
+        }
+
+        if micro_count != 1 || segments.last() != Some(&CalVerSegment::Micro) {
+            return Err(format!(
+                "CalVer format `{value}` must end with a single `MICRO` segment"
+            ));
+        }
+
+        Ok(CalVerFormat(segments))
+    }
+}
+
+impl From<CalVerFormat> for String {
+    fn from(format: CalVerFormat) -> Self {
+        format.to_string()
+    }
+}
+
+impl fmt::Display for CalVerFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let tokens: Vec<&str> = self.0.iter().map(CalVerSegment::token).collect();
+        write!(f, "{}", tokens.join("."))
+    }
+}
+
+impl CalVerFormat {
+    /// Parse a calendar version, returns `None` if it does not match the format.
+    pub fn parse(&self, version: &str) -> Option<Version> {
+        let (version, build) = match version.split_once('+') {
+            Some((version, build)) => (version, BuildMetadata::new(build).ok()?),
+            None => (version, BuildMetadata::EMPTY),
+        };
+
+        let (version, pre) = match version.split_once('-') {
+            Some((version, pre)) => (version, Prerelease::new(pre).ok()?),
+            None => (version, Prerelease::EMPTY),
+        };
+
+        let parts: Vec<&str> = version.split('.').collect();
+        if parts.len() != self.0.len() {
+            return None;
+        }
+
+        let mut numbers = [0; 3];
+        for (idx, (segment, part)) in self.0.iter().zip(parts).enumerate() {
+            if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+
+            let value = part.parse().ok()?;
+            if !segment.is_valid(value) {
+                return None;
+            }
+
+            numbers[idx] = value;
+        }
+
+        let [major, minor, patch] = numbers;
+        let mut version = Version::new(major, minor, patch);
+        version.pre = pre;
+        version.build = build;
+        Some(version)
+    }
+
+    /// Display the given version according to the format.
+    pub fn format(&self, version: &Version) -> String {
+        let numbers = [version.major, version.minor, version.patch];
+        let mut output = self
+            .0
+            .iter()
+            .zip(numbers)
+            .map(|(segment, value)| {
+                if segment.is_padded() {
+                    format!("{value:02}")
+                } else {
+                    value.to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(".");
+
+        if !version.pre.is_empty() {
+            output.push('-');
+            output.push_str(version.pre.as_str());
+        }
+
+        if !version.build.is_empty() {
+            output.push('+');
+            output.push_str(version.build.as_str());
         }
+
+        output
+    }
+
+    /// Get the next calendar version for the given date. The micro counter is
+    /// incremented if the date segments are unchanged and reset otherwise.
+    pub fn next(&self, current: &Version, date: NaiveDate) -> Version {
+        let current = [current.major, current.minor, current.patch];
+        let mut next = [0; 3];
+        let mut same_date = true;
+        let week_based = self.0.iter().any(CalVerSegment::is_week);
+
+        for (idx, segment) in self.0.iter().enumerate() {
+            if *segment == CalVerSegment::Micro {
+                next[idx] = if same_date { current[idx] + 1 } else { 0 };
+            } else {
+                next[idx] = segment.value_for(date, week_based);
+                same_date = same_date && next[idx] == current[idx];
+            }
+        }
+
+        let [major, minor, patch] = next;
+        Version::new(major, minor, patch)
     }
 }
 
@@ -51,5 +323,93 @@ impl PartialOrd for Increment {
 // Auto version tests resides in test/ dir since it rely on git log
 // To generate the version
 mod test {
-    // TODO
+    use crate::conventional::version::CalVerFormat;
+    use chrono::NaiveDate;
+    use semver::Version;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_parse_calver_format() -> Result<(), String> {
+        let format = CalVerFormat::try_from("YY.0M.MICRO".to_string())?;
+
+        assert_that!(format.to_string()).is_equal_to("YY.0M.MICRO".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_calver_format_without_trailing_micro() {
+        let format = CalVerFormat::try_from("MICRO.YYYY".to_string());
+
+        assert_that!(format).is_err();
+    }
+
+    #[test]
+    fn should_reject_unknown_calver_token() {
+        let format = CalVerFormat::try_from("YYYY.MMM.MICRO".to_string());
+
+        assert_that!(format).is_err();
+    }
+
+    #[test]
+    fn should_parse_and_format_padded_calver() -> Result<(), String> {
+        let format = CalVerFormat::try_from("YYYY.0M.MICRO".to_string())?;
+
+        let version = format.parse("2023.02.1-rc.1");
+
+        assert_that!(version)
+            .is_some()
+            .is_equal_to(Version::parse("2023.2.1-rc.1").unwrap());
+        assert_that!(format.format(&version.unwrap())).is_equal_to("2023.02.1-rc.1".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_parse_invalid_calver() {
+        let format = CalVerFormat::default();
+
+        assert_that!(format.parse("2023.13.0")).is_none();
+        assert_that!(format.parse("2023.1")).is_none();
+        assert_that!(format.parse("v2023.1.0")).is_none();
+    }
+
+    #[test]
+    fn should_increment_micro_on_same_date() {
+        let format = CalVerFormat::default();
+        let date = NaiveDate::from_ymd_opt(2023, 2, 14).unwrap();
+
+        let next = format.next(&Version::new(2023, 2, 3), date);
+
+        assert_that!(next).is_equal_to(Version::new(2023, 2, 4));
+    }
+
+    #[test]
+    fn should_reset_micro_when_date_changes() {
+        let format = CalVerFormat::try_from("YY.0M.MICRO".to_string()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+
+        let next = format.next(&Version::new(23, 2, 3), date);
+
+        assert_that!(next).is_equal_to(Version::new(23, 3, 0));
+    }
+
+    #[test]
+    fn should_use_iso_week_year_with_week_segment() {
+        let format = CalVerFormat::try_from("YYYY.0W.MICRO".to_string()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+
+        let next = format.next(&Version::new(2024, 52, 3), date);
+
+        assert_that!(next).is_equal_to(Version::new(2025, 1, 0));
+        assert_that!(next > Version::new(2024, 52, 3)).is_true();
+    }
+
+    #[test]
+    fn should_use_calendar_year_without_week_segment() {
+        let format = CalVerFormat::default();
+        let date = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+
+        let next = format.next(&Version::new(2024, 12, 3), date);
+
+        assert_that!(next).is_equal_to(Version::new(2024, 12, 4));
+    }
 }
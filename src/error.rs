@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug, Display, Formatter};
+use std::path::PathBuf;
 
 use crate::git::oid::OidOf;
 
@@ -38,6 +39,9 @@ pub(crate) struct BumpError {
     pub(crate) cause: String,
     pub(crate) version: String,
     pub(crate) stash_number: u32,
+    /// The last lines output by the failed hook
+    pub(crate) hook_output: Vec<String>,
+    pub(crate) hook_log: Option<PathBuf>,
 }
 
 impl Display for BumpError {
@@ -54,6 +58,19 @@ impl Display for BumpError {
         \tyou can run `git stash apply stash@{}` to restore these changes.",
             stash_ref, self.stash_number
         );
-        write!(f, "{header}\n{suggestion}")
+        write!(f, "{header}\n{suggestion}")?;
+
+        if !self.hook_output.is_empty() {
+            write!(f, "\n\n\tLast lines of the hook output:\n")?;
+            for line in &self.hook_output {
+                write!(f, "\n\t  {line}")?;
+            }
+        }
+
+        if let Some(log) = &self.hook_log {
+            write!(f, "\n\n\tThe output of every hook run is logged in {log:?}")?;
+        }
+
+        Ok(())
     }
 }
@@ -18,11 +18,9 @@ impl Repository {
             let tip = &self.0.find_commit(head_target)?;
 
             self.commit_or_signed_commit(&sig, message, &tree, &[tip], sign)
-                .map_err(Git2Error::from)
         } else if is_empty && has_delta {
             // First repo commit
             self.commit_or_signed_commit(&sig, message, &tree, &[], sign)
-                .map_err(Git2Error::from)
         } else {
             let statuses = self.get_statuses()?;
             let statuses = if statuses.0.is_empty() {
@@ -87,7 +85,11 @@ fn gpg_sign_string(key: Option<String>, content: &str) -> Result<String, Git2Err
 
     {
         let stdin = child.stdin.as_mut().unwrap();
-        stdin.write_all(content.as_bytes())?;
+        if let Err(err) = stdin.write_all(content.as_bytes()) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(err.into());
+        }
     }
 
     child.wait_with_output().map(|output| {
@@ -2,7 +2,7 @@ use crate::git::repository::Repository;
 use git2::{Diff, DiffOptions};
 
 impl Repository {
-    pub(crate) fn get_diff(&self, include_untracked: bool) -> Option<Diff> {
+    pub(crate) fn get_diff(&self, include_untracked: bool) -> Option<Diff<'_>> {
         let mut options = DiffOptions::new();
         options.include_untracked(include_untracked);
 
@@ -1,3 +1,4 @@
+use crate::conventional::version::CalVerFormat;
 use crate::git::status::Statuses;
 use colored::Colorize;
 use git2::Error;
@@ -34,10 +35,18 @@ pub enum TagError {
         tag: String,
         err: semver::Error,
     },
+    CalVerError {
+        tag: String,
+        format: String,
+    },
     InvalidPrefixError {
         prefix: String,
         tag: String,
     },
+    FormatMismatch {
+        tag: String,
+        format: String,
+    },
     NotFound {
         tag: String,
         err: git2::Error,
@@ -71,6 +80,20 @@ impl TagError {
             err,
         }
     }
+
+    pub fn format_mismatch(tag: &str, format: &str) -> Self {
+        TagError::FormatMismatch {
+            tag: tag.to_string(),
+            format: format.to_string(),
+        }
+    }
+
+    pub fn calver(tag: &str, format: &CalVerFormat) -> Self {
+        TagError::CalVerError {
+            tag: tag.to_string(),
+            format: format.to_string(),
+        }
+    }
 }
 
 impl Display for Git2Error {
@@ -146,9 +169,15 @@ impl Display for TagError {
                 writeln!(f, "tag `{tag}` is not SemVer compliant")?;
                 writeln!(f, "\tcause: {err}")
             }
+            TagError::CalVerError { tag, format } => {
+                writeln!(f, "tag `{tag}` does not match CalVer format `{format}`")
+            }
             TagError::InvalidPrefixError { prefix, tag } => {
                 writeln!(f, "Expected a tag with prefix {prefix}, got {tag}")
             }
+            TagError::FormatMismatch { tag, format } => {
+                writeln!(f, "tag `{tag}` does not match tag format `{format}`")
+            }
             TagError::NotFound { tag, err } => {
                 writeln!(f, "tag {tag} not found")?;
                 writeln!(f, "\tcause: {err}")
@@ -7,7 +7,7 @@ use std::path::Path;
 
 use crate::{CocoGitto, HookType};
 
-use crate::settings::BumpProfile;
+use crate::settings::{BumpProfile, HookSetting};
 use anyhow::{anyhow, Result};
 
 pub(crate) static PRE_PUSH_HOOK: &[u8] = include_bytes!("assets/pre-push");
@@ -17,17 +17,23 @@ const PRE_PUSH_HOOK_PATH: &str = ".git/hooks/pre-push";
 
 pub trait Hooks {
     fn bump_profiles(&self) -> &HashMap<String, BumpProfile>;
-    fn pre_bump_hooks(&self) -> &Vec<String>;
-    fn post_bump_hooks(&self) -> &Vec<String>;
+    fn pre_bump_hooks(&self) -> &Vec<HookSetting>;
+    fn post_bump_hooks(&self) -> &Vec<HookSetting>;
+    fn pre_commit_hooks(&self) -> &Vec<HookSetting>;
+    fn post_changelog_hooks(&self) -> &Vec<HookSetting>;
+    fn on_bump_failure_hooks(&self) -> &Vec<HookSetting>;
 
-    fn get_hooks(&self, hook_type: HookType) -> &Vec<String> {
+    fn get_hooks(&self, hook_type: HookType) -> &Vec<HookSetting> {
         match hook_type {
             HookType::PreBump => self.pre_bump_hooks(),
             HookType::PostBump => self.post_bump_hooks(),
+            HookType::PreCommit => self.pre_commit_hooks(),
+            HookType::PostChangelog => self.post_changelog_hooks(),
+            HookType::BumpFailure => self.on_bump_failure_hooks(),
         }
     }
 
-    fn get_profile_hooks(&self, profile: &str, hook_type: HookType) -> &Vec<String> {
+    fn get_profile_hooks(&self, profile: &str, hook_type: HookType) -> &Vec<HookSetting> {
         let profile = self
             .bump_profiles()
             .get(profile)
@@ -35,6 +41,10 @@ pub trait Hooks {
         match hook_type {
             HookType::PreBump => &profile.pre_bump_hooks,
             HookType::PostBump => &profile.post_bump_hooks,
+            HookType::PostChangelog => &profile.post_changelog_hooks,
+            HookType::BumpFailure => &profile.on_bump_failure_hooks,
+            // Bump profiles don't apply to commits
+            HookType::PreCommit => self.pre_commit_hooks(),
         }
     }
 }
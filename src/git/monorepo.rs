@@ -1,11 +1,12 @@
 use crate::git::repository::Repository;
+use git2::Oid;
 
 use crate::{Tag, TagError};
 
 impl Repository {
     /// Get the latest SemVer tag for a given monorepo package.
     pub fn get_latest_package_tag(&self, package_prefix: &str) -> Result<Tag, TagError> {
-        let tags: Vec<Tag> = self.all_tags()?;
+        let tags: Vec<Tag> = self.latest_tag_candidates()?;
 
         tags.into_iter()
             .filter(|tag| {
@@ -17,6 +18,48 @@ impl Repository {
             .max()
             .ok_or(TagError::NoTag)
     }
+
+    /// Get the latest SemVer tag without pre-release identifier for a given monorepo package.
+    pub fn get_latest_stable_package_tag(&self, package_prefix: &str) -> Result<Tag, TagError> {
+        let tags: Vec<Tag> = self.latest_tag_candidates()?;
+
+        tags.into_iter()
+            .filter(|tag| tag.version.pre.is_empty())
+            .filter(|tag| {
+                tag.package
+                    .as_ref()
+                    .map(|package| package == package_prefix)
+                    .unwrap_or_default()
+            })
+            .max()
+            .ok_or(TagError::NoTag)
+    }
+
+    /// Get the latest tag of the given package among the ancestors of `commit`, ignoring
+    /// the tags on `commit` itself.
+    pub(crate) fn get_latest_package_tag_before(
+        &self,
+        package: &str,
+        commit: Oid,
+    ) -> Result<Tag, TagError> {
+        let parent = self
+            .0
+            .find_commit(commit)
+            .ok()
+            .and_then(|commit| commit.parent_id(0).ok());
+
+        let parent = match parent {
+            Some(parent) => parent,
+            None => return Err(TagError::NoTag),
+        };
+
+        self.all_tags()?
+            .into_iter()
+            .filter(|tag| tag.package.as_deref() == Some(package))
+            .filter(|tag| self.is_ancestor_of(tag, parent))
+            .max()
+            .ok_or(TagError::NoTag)
+    }
 }
 
 #[cfg(test)]
@@ -20,6 +20,16 @@ impl OidOf {
             OidOf::Other(o) => o,
         }
     }
+
+    /// A revspec resolving to this oid, tag names are kept so ranges ending on a tag
+    /// still resolve to it
+    pub(crate) fn revspec(&self) -> String {
+        match self {
+            OidOf::Tag(tag) => tag.revspec(),
+            OidOf::Head(_) => "HEAD".to_string(),
+            OidOf::Other(oid) => oid.to_string(),
+        }
+    }
 }
 
 impl Display for OidOf {
@@ -34,6 +34,11 @@ impl Repository {
         self.0.workdir()
     }
 
+    /// Whether the given path, relative to the repository root, is ignored by git
+    pub(crate) fn is_path_ignored(&self, path: &Path) -> bool {
+        self.0.is_path_ignored(path).unwrap_or(false)
+    }
+
     pub(crate) fn add_all(&self) -> Result<(), Git2Error> {
         let mut index = self.0.index()?;
         index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
@@ -44,7 +49,7 @@ impl Repository {
         self.get_head_commit().map(|commit| commit.id())
     }
 
-    pub(crate) fn get_head_commit(&self) -> Result<Git2Commit, Git2Error> {
+    pub(crate) fn get_head_commit(&self) -> Result<Git2Commit<'_>, Git2Error> {
         let head_ref = self.0.head();
         match head_ref {
             Ok(head) => head.peel_to_commit().map_err(Git2Error::PeelToCommitError),
@@ -61,7 +66,7 @@ impl Repository {
             .map_err(Git2Error::CommitNotFound)
     }
 
-    pub(crate) fn get_head(&self) -> Option<Object> {
+    pub(crate) fn get_head(&self) -> Option<Object<'_>> {
         self.tree_to_treeish(Some(&"HEAD".to_string()))
             .ok()
             .flatten()
@@ -85,7 +90,7 @@ impl Repository {
     pub(crate) fn tree_to_treeish(
         &self,
         arg: Option<&String>,
-    ) -> Result<Option<Object>, git2::Error> {
+    ) -> Result<Option<Object<'_>>, git2::Error> {
         let arg = match arg {
             Some(s) => s,
             None => return Ok(None),
@@ -94,6 +99,13 @@ impl Repository {
         let tree = obj.peel(ObjectType::Tree)?;
         Ok(Some(tree))
     }
+
+    /// Resolve a revision (sha, tag, branch...) to the commit it points to
+    pub(crate) fn resolve_commit(&self, rev: &str) -> Result<Oid, git2::Error> {
+        let obj = self.0.revparse_single(rev)?;
+        let commit = obj.peel(ObjectType::Commit)?;
+        Ok(commit.id())
+    }
 }
 
 impl Debug for Repository {
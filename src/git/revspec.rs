@@ -66,7 +66,7 @@ impl From<(&str, &str)> for RevspecPattern {
 
 impl Repository {
     /// Return a [`CommitRange`] containing all commit in the current repository
-    pub fn all_commits(&self) -> Result<CommitRange, Git2Error> {
+    pub fn all_commits(&self) -> Result<CommitRange<'_>, Git2Error> {
         let mut revwalk = self.0.revwalk()?;
         revwalk.push_head()?;
         let mut commits = vec![];
@@ -99,7 +99,10 @@ impl Repository {
         Ok(CommitRange { from, to, commits })
     }
 
-    pub(crate) fn get_release_range(&self, pattern: RevspecPattern) -> Result<Release, Git2Error> {
+    pub(crate) fn get_release_range(
+        &self,
+        pattern: RevspecPattern,
+    ) -> Result<Release<'_>, Git2Error> {
         let target = if let Some(target) = pattern.from {
             self.resolve_oid_of(&target)
         } else {
@@ -130,7 +133,7 @@ impl Repository {
         mut release: Release<'a>,
         target: &Oid,
     ) -> Result<Release<'a>, Git2Error> {
-        let pattern = format!("..{}", release.from);
+        let pattern = format!("..{}", release.from.revspec());
         let pattern = RevspecPattern::from(pattern.as_str());
         let range = self.get_commit_range(&pattern)?;
 
@@ -160,7 +163,7 @@ impl Repository {
     /// Return a commit range
     /// `from` : either a tag or an oid, latest tag if none, fallbacks to first commit
     /// `to`: HEAD if none
-    pub fn get_commit_range(&self, pattern: &RevspecPattern) -> Result<CommitRange, Git2Error> {
+    pub fn get_commit_range(&self, pattern: &RevspecPattern) -> Result<CommitRange<'_>, Git2Error> {
         let from = pattern.from.as_deref();
         let to = pattern.to.as_deref();
 
@@ -200,7 +203,7 @@ impl Repository {
         };
 
         // Resolve shorthands and tags
-        let spec = format!("{from}..{to}");
+        let spec = format!("{}..{to}", from.revspec());
         // Attempt to resolve tag names, fallback to oid
         let to = maybe_to_tag
             .map(OidOf::Tag)
@@ -215,11 +218,50 @@ impl Repository {
         &self,
         pattern: &RevspecPattern,
         package: &str,
-    ) -> Result<CommitRange, Git2Error> {
+    ) -> Result<CommitRange<'_>, Git2Error> {
         let mut commit_range = self.get_commit_range(pattern)?;
-        let mut commits = vec![];
-        let package = SETTINGS.packages.get(package).expect("package exists");
-        for commit in commit_range.commits {
+        commit_range.commits = self.filter_package_commits(commit_range.commits, package)?;
+        Ok(commit_range)
+    }
+
+    /// Return the commit range of a package
+    /// `from` : either a tag or an oid, latest package tag before `to` if none, fallbacks to first commit
+    /// `to`: HEAD if none
+    pub fn get_package_commit_range(
+        &self,
+        mut pattern: RevspecPattern,
+        package: &str,
+    ) -> Result<CommitRange<'_>, Git2Error> {
+        if pattern.from.is_none() {
+            let to = match pattern.to.as_deref() {
+                Some(to) => self.0.revparse_single(to)?.peel_to_commit()?.id(),
+                None => self.get_head_commit_oid()?,
+            };
+
+            let from = match self.get_latest_package_tag_before(package, to) {
+                Ok(tag) => tag.revspec(),
+                Err(_) => self.get_first_commit()?.to_string(),
+            };
+
+            pattern.from = Some(from);
+        }
+
+        self.get_commit_range_for_package(&pattern, package)
+    }
+
+    /// Keep only the commits changing files that belong to the given package
+    pub(crate) fn filter_package_commits<'a>(
+        &self,
+        commits: Vec<Commit<'a>>,
+        package: &str,
+    ) -> Result<Vec<Commit<'a>>, Git2Error> {
+        let mut package_commits = vec![];
+        let package = SETTINGS
+            .packages
+            .get(package)
+            .expect("package exists")
+            .file_matcher();
+        for commit in commits {
             let parent = commit.parent(0).ok().map(|commit| commit.id().to_string());
 
             let parent_tree = self.tree_to_treeish(parent.as_ref())?;
@@ -240,35 +282,34 @@ impl Repository {
 
             for delta in diff.deltas() {
                 if let Some(old) = delta.old_file().path() {
-                    if old.starts_with(&package.path) {
-                        commits.push(commit);
+                    if package.is_match(old) {
+                        package_commits.push(commit);
                         break;
                     }
                 }
 
                 if let Some(new) = delta.new_file().path() {
-                    if new.starts_with(&package.path) {
-                        commits.push(commit);
+                    if package.is_match(new) {
+                        package_commits.push(commit);
                         break;
                     }
                 }
             }
         }
 
-        commit_range.commits = commits;
-        Ok(commit_range)
+        Ok(package_commits)
     }
 
     pub fn get_commit_range_for_monorepo_global(
         &self,
         pattern: &RevspecPattern,
-    ) -> Result<CommitRange, Git2Error> {
+    ) -> Result<CommitRange<'_>, Git2Error> {
         let mut commit_range = self.get_commit_range(pattern)?;
         let mut commits = vec![];
-        let package_paths: Vec<_> = SETTINGS
+        let packages: Vec<_> = SETTINGS
             .packages
             .values()
-            .map(|package| &package.path)
+            .map(|package| package.file_matcher())
             .collect();
 
         for commit in commit_range.commits {
@@ -285,14 +326,14 @@ impl Repository {
 
             for delta in diff.deltas() {
                 if let Some(old) = delta.old_file().path() {
-                    if package_paths.iter().all(|path| !old.starts_with(path)) {
+                    if packages.iter().all(|package| !package.is_match(old)) {
                         commits.push(commit);
                         break;
                     }
                 }
 
                 if let Some(new) = delta.new_file().path() {
-                    if package_paths.iter().all(|path| !new.starts_with(path)) {
+                    if packages.iter().all(|package| !package.is_match(new)) {
                         commits.push(commit);
                         break;
                     }
@@ -326,7 +367,7 @@ impl Repository {
             })
     }
 
-    fn get_commit_range_from_spec(&self, spec: &str) -> Result<Vec<Commit>, Git2Error> {
+    fn get_commit_range_from_spec(&self, spec: &str) -> Result<Vec<Commit<'_>>, Git2Error> {
         let mut revwalk = self.0.revwalk()?;
 
         revwalk.push_range(spec)?;
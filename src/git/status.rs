@@ -105,7 +105,7 @@ impl From<Git2Statuses<'_>> for Statuses {
     }
 }
 
-impl<'a, 'b: 'a> From<Git2StatusEntry<'b>> for Status {
+impl<'b> From<Git2StatusEntry<'b>> for Status {
     fn from(status: Git2StatusEntry<'b>) -> Self {
         let path = status.path().unwrap_or("invalid utf8 path").to_string();
         match status.status() {
@@ -184,7 +184,7 @@ mod test {
 
         // Assert
         assert_that!(statuses.iter())
-            .contains(&super::Status::Untracked(Changes::New("file".into())));
+            .contains(super::Status::Untracked(Changes::New("file".into())));
         assert_that!(statuses).has_length(1);
         Ok(())
     }
@@ -1,8 +1,8 @@
-use crate::conventional::version::Increment;
+use crate::conventional::version::{Increment, VersionScheme};
 use crate::git::error::{Git2Error, TagError};
 use crate::git::repository::Repository;
 use crate::SETTINGS;
-use git2::Oid;
+use git2::{ObjectType, Oid};
 use semver::Version;
 use std::cmp::Ordering;
 use std::fmt;
@@ -52,15 +52,100 @@ impl Repository {
             .map_err(Git2Error::from)
     }
 
+    /// Create a lightweight tag named `name` on the commit `oid` points to
+    pub(crate) fn create_tag_at(&self, name: &str, oid: Oid) -> Result<(), Git2Error> {
+        let commit = self.0.find_object(oid, None)?.peel(ObjectType::Commit)?;
+        self.0
+            .tag_lightweight(name, &commit, false)
+            .map(|_| ())
+            .map_err(Git2Error::from)
+    }
+
     /// Get the latest tag, will ignore package tag if on a monorepo
     pub(crate) fn get_latest_tag(&self) -> Result<Tag, TagError> {
-        let tags: Vec<Tag> = self.all_tags()?;
+        let tags: Vec<Tag> = self.latest_tag_candidates()?;
         tags.into_iter()
             .filter(|tag| tag.package.is_none())
             .max()
             .ok_or(TagError::NoTag)
     }
 
+    /// Get the latest tag without pre-release identifier, will ignore package tag if on a monorepo
+    pub(crate) fn get_latest_stable_tag(&self) -> Result<Tag, TagError> {
+        let tags: Vec<Tag> = self.latest_tag_candidates()?;
+        tags.into_iter()
+            .filter(|tag| tag.package.is_none() && tag.version.pre.is_empty())
+            .max()
+            .ok_or(TagError::NoTag)
+    }
+
+    /// Get the next pre-release number for `identifier` on the given version,
+    /// ex: `1`, or `4` if `1.0.0-beta.3` exists.
+    pub(crate) fn next_prerelease_number(
+        &self,
+        version: &Version,
+        package: Option<&str>,
+        identifier: &str,
+    ) -> Result<u64, TagError> {
+        let prefix = format!("{identifier}.");
+        let latest = self
+            .all_tags()?
+            .into_iter()
+            .filter(|tag| tag.package.as_deref() == package)
+            .filter(|tag| {
+                tag.version.major == version.major
+                    && tag.version.minor == version.minor
+                    && tag.version.patch == version.patch
+            })
+            .filter_map(|tag| {
+                tag.version
+                    .pre
+                    .as_str()
+                    .strip_prefix(&prefix)
+                    .and_then(|number| number.parse::<u64>().ok())
+            })
+            .max();
+
+        Ok(latest.map(|number| number + 1).unwrap_or(1))
+    }
+
+    /// All tags eligible as the latest tag, when `reachable_tags_only` is set
+    /// tags that are not ancestors of HEAD are ignored.
+    pub(crate) fn latest_tag_candidates(&self) -> Result<Vec<Tag>, TagError> {
+        let tags = self.all_tags()?;
+        if !SETTINGS.reachable_tags_only {
+            return Ok(tags);
+        }
+
+        let head = match self.get_head_commit_oid() {
+            Ok(head) => head,
+            Err(_) => return Ok(tags),
+        };
+
+        Ok(tags
+            .into_iter()
+            .filter(|tag| self.is_ancestor_of(tag, head))
+            .collect())
+    }
+
+    /// Returns true if the commit targeted by `tag` is `commit` or one of its ancestors
+    pub(crate) fn is_ancestor_of(&self, tag: &Tag, commit: Oid) -> bool {
+        let target = tag
+            .oid()
+            .and_then(|oid| self.0.find_object(*oid, None).ok())
+            .and_then(|object| object.peel_to_commit().ok())
+            .map(|commit| commit.id());
+
+        match target {
+            Some(target) if target == commit => true,
+            Some(target) => self
+                .0
+                .graph_descendant_of(commit, target)
+                .unwrap_or_default(),
+            None => false,
+        }
+    }
+
     pub(crate) fn all_tags(&self) -> Result<Vec<Tag>, TagError> {
         Ok(self
             .tags()?
@@ -75,27 +160,45 @@ impl Repository {
             .map(|tag| tag.oid_unchecked().to_owned())
     }
 
-    fn tags(&self) -> Result<Vec<String>, TagError> {
+    /// All tag names, with their raw name, matching the current or a legacy tag prefix
+    pub(crate) fn tags(&self) -> Result<Vec<String>, TagError> {
         let packages: Vec<&str> = SETTINGS
             .packages
             .keys()
             .map(|profile| -> &str { profile })
             .collect();
 
-        let pattern = SETTINGS
-            .tag_prefix
-            .as_ref()
-            .map(|prefix| format!("{prefix}*"));
+        let pattern = match SETTINGS.tag_format(None) {
+            Some(format) => Some(TagFormat::new(format, None).before)
+                .filter(|before| !before.is_empty())
+                .map(|before| format!("{before}*")),
+            None => SETTINGS
+                .tag_prefix
+                .as_ref()
+                .map(|prefix| format!("{prefix}*")),
+        };
+
+        let legacy_patterns = SETTINGS.legacy_tag_prefixes.iter().map(|prefix| {
+            if prefix.is_empty() {
+                None
+            } else {
+                Some(format!("{prefix}*"))
+            }
+        });
 
         // Collect non packages tags
-        let mut tags: Vec<String> = self
-            .0
-            .tag_names(pattern.as_deref())
-            .map_err(|err| TagError::NoMatchFound { pattern, err })?
-            .iter()
-            .flatten()
-            .map(str::to_string)
-            .collect();
+        let mut tags: Vec<String> = vec![];
+        for pattern in std::iter::once(pattern).chain(legacy_patterns) {
+            let names = self
+                .0
+                .tag_names(pattern.as_deref())
+                .map_err(|err| TagError::NoMatchFound { pattern, err })?;
+
+            tags.extend(names.iter().flatten().map(str::to_string));
+        }
+
+        tags.sort();
+        tags.dedup();
 
         // Extends with packages tags if we are in a mono-repository context
         if !packages.is_empty() {
@@ -103,7 +206,11 @@ impl Repository {
             let package_tags = package_tags
                 .into_iter()
                 .flatten()
-                .filter(|tag| packages.iter().any(|package| tag.starts_with(package)))
+                .filter(|tag| {
+                    packages
+                        .iter()
+                        .any(|package| Tag::strip_package(tag, package).is_some())
+                })
                 .map(str::to_string);
 
             tags.extend(package_tags);
@@ -119,6 +226,9 @@ pub struct Tag {
     pub prefix: Option<String>,
     pub version: Version,
     pub oid: Option<Oid>,
+    /// The reference name the tag was read from, it differs from the displayed tag
+    /// when matched through `legacy_tag_prefixes`
+    pub(crate) raw: Option<String>,
 }
 
 impl Ord for Tag {
@@ -135,6 +245,8 @@ impl PartialEq for Tag {
     }
 }
 
+// Tags belonging to different packages or using different prefixes are not comparable
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd<Tag> for Tag {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         if self.package != other.package {
@@ -177,36 +289,35 @@ impl Tag {
             prefix: SETTINGS.tag_prefix.clone(),
             version,
             oid: None,
+            raw: None,
         }
     }
 
+    /// A revspec resolving to this tag, its reference name if it exists in the repository
+    pub(crate) fn revspec(&self) -> String {
+        self.raw.clone().unwrap_or_else(|| self.to_string())
+    }
+
     pub(crate) fn oid(&self) -> Option<&Oid> {
         self.oid.as_ref()
     }
 
     pub(crate) fn from_str(raw: &str, oid: Option<Oid>) -> Result<Tag, TagError> {
         let prefix = SETTINGS.tag_prefix.as_ref();
+        let reference = oid.map(|_| raw.to_string());
 
         let package_tag: Option<Tag> = SETTINGS
             .packages
             .keys()
             .filter_map(|package_name| {
-                raw.strip_prefix(package_name)
-                    .zip(SETTINGS.monorepo_separator())
-                    .and_then(|(remains, prefix)| remains.strip_prefix(prefix))
-                    .map(|remains| {
-                        SETTINGS
-                            .tag_prefix
-                            .as_ref()
-                            .and_then(|prefix| remains.strip_prefix(prefix))
-                            .unwrap_or(remains)
-                    })
-                    .and_then(|version| Version::parse(version).ok())
+                Tag::strip_package(raw, package_name)
+                    .and_then(|version| Tag::parse_version(raw, version).ok())
                     .map(|version| Tag {
                         package: Some(package_name.to_string()),
                         prefix: SETTINGS.tag_prefix.clone(),
                         version,
                         oid,
+                        raw: reference.clone(),
                     })
             })
             .next();
@@ -214,18 +325,71 @@ impl Tag {
         if let Some(tag) = package_tag {
             Ok(tag)
         } else {
-            let version = prefix
-                .and_then(|prefix| raw.strip_prefix(prefix))
-                .unwrap_or(raw);
-
-            let version = Version::parse(version).map_err(|err| TagError::semver(raw, err))?;
+            let version = match SETTINGS.tag_format(None) {
+                Some(format) => TagFormat::new(format, None)
+                    .parse(raw)
+                    .ok_or_else(|| TagError::format_mismatch(raw, format))?,
+                None => Tag::strip_prefix(raw),
+            };
+            let version = Tag::parse_version(raw, version)?;
 
             Ok(Tag {
                 package: None,
                 prefix: prefix.cloned(),
                 version,
                 oid,
+                raw: reference,
+            })
+        }
+    }
+
+    /// Get the version part of a tag name belonging to `package`, if any.
+    fn strip_package<'a>(raw: &'a str, package: &str) -> Option<&'a str> {
+        match SETTINGS.tag_format(Some(package)) {
+            Some(format) => TagFormat::new(format, Some(package)).parse(raw),
+            None => raw
+                .strip_prefix(package)
+                .zip(SETTINGS.monorepo_separator())
+                .and_then(|(remains, separator)| remains.strip_prefix(separator))
+                .map(Tag::strip_prefix),
+        }
+    }
+
+    /// Remove the configured tag prefix, or a legacy one, from the given tag name.
+    /// Legacy tags are read as if they had the current prefix.
+    fn strip_prefix(raw: &str) -> &str {
+        SETTINGS
+            .tag_prefix
+            .as_ref()
+            .and_then(|prefix| raw.strip_prefix(prefix.as_str()))
+            .or_else(|| {
+                SETTINGS
+                    .legacy_tag_prefixes
+                    .iter()
+                    .filter(|prefix| !prefix.is_empty())
+                    .find_map(|prefix| raw.strip_prefix(prefix.as_str()))
             })
+            .unwrap_or(raw)
+    }
+
+    /// Parse the `version` part of the `tag` according to the configured version scheme.
+    pub(crate) fn parse_version(tag: &str, version: &str) -> Result<Version, TagError> {
+        match SETTINGS.version_scheme {
+            VersionScheme::Semver => {
+                Version::parse(version).map_err(|err| TagError::semver(tag, err))
+            }
+            VersionScheme::Calver => SETTINGS
+                .calver_format
+                .parse(version)
+                .ok_or_else(|| TagError::calver(tag, &SETTINGS.calver_format)),
+        }
+    }
+
+    /// Display a version according to the configured version scheme.
+    pub(crate) fn format_version(version: &Version) -> String {
+        match SETTINGS.version_scheme {
+            VersionScheme::Semver => version.to_string(),
+            VersionScheme::Calver => SETTINGS.calver_format.format(version),
         }
     }
 
@@ -248,8 +412,11 @@ impl Tag {
 
 impl fmt::Display for Tag {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let version = self.version.to_string();
-        if let Some((package, prefix)) = self.package.as_ref().zip(self.prefix.as_ref()) {
+        let version = Tag::format_version(&self.version);
+        if let Some(format) = SETTINGS.tag_format(self.package.as_deref()) {
+            let format = TagFormat::new(format, self.package.as_deref());
+            write!(f, "{}", format.format(&version))
+        } else if let Some((package, prefix)) = self.package.as_ref().zip(self.prefix.as_ref()) {
             let separator = SETTINGS.monorepo_separator().unwrap_or_else(||
                 panic!("Found a tag with monorepo package prefix but there are no packages in cog.toml")
             );
@@ -268,6 +435,40 @@ impl fmt::Display for Tag {
     }
 }
 
+/// A tag name template with `{{version}}` and `{{package}}` placeholders,
+/// split around the version once the package name is rendered.
+struct TagFormat {
+    before: String,
+    after: String,
+}
+
+impl TagFormat {
+    fn new(template: &str, package: Option<&str>) -> Self {
+        let template = template
+            .replace("{{ version }}", "{{version}}")
+            .replace("{{ package }}", "{{package}}")
+            .replace("{{package}}", package.unwrap_or_default());
+
+        // A template without version placeholder is a plain prefix
+        let (before, after) = template
+            .split_once("{{version}}")
+            .unwrap_or((&template, ""));
+
+        TagFormat {
+            before: before.to_string(),
+            after: after.to_string(),
+        }
+    }
+
+    fn parse<'a>(&self, raw: &'a str) -> Option<&'a str> {
+        raw.strip_prefix(&self.before)?.strip_suffix(&self.after)
+    }
+
+    fn format(&self, version: &str) -> String {
+        format!("{}{version}{}", self.before, self.after)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::git::repository::Repository;
@@ -332,6 +533,7 @@ mod test {
             prefix: None,
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         });
 
         Ok(())
@@ -356,6 +558,7 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         });
 
         Ok(())
@@ -382,6 +585,7 @@ mod test {
             prefix: None,
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         });
 
         Ok(())
@@ -409,6 +613,7 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         });
 
         Ok(())
@@ -437,6 +642,7 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         });
 
         Ok(())
@@ -542,6 +748,65 @@ mod test {
         Ok(())
     }
 
+    #[sealed_test]
+    fn get_latest_tag_reachable_from_head() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        let settings = Settings {
+            reachable_tags_only: true,
+            ..Default::default()
+        };
+        let settings = toml::to_string(&settings)?;
+
+        run_cmd!(
+            echo $settings > cog.toml;
+            git add .;
+            git commit -m "first commit";
+            git tag 1.0.0;
+            git branch release/1.x;
+            git commit --allow-empty -m "second commit";
+            git tag 2.0.0;
+            git checkout release/1.x;
+            git commit --allow-empty -m "maintenance commit";
+        )?;
+
+        // Act
+        let tag = repo.get_latest_tag()?;
+
+        // Assert
+        assert_that!(tag.to_string()).is_equal_to("1.0.0".to_string());
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn get_latest_tag_with_legacy_prefix() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        let settings = Settings {
+            tag_prefix: Some("v".to_string()),
+            legacy_tag_prefixes: vec!["release-".to_string()],
+            ..Default::default()
+        };
+        let settings = toml::to_string(&settings)?;
+
+        run_cmd!(
+            echo $settings > cog.toml;
+            git add .;
+            git commit -m "first commit";
+            git tag release-1.0.0;
+            git commit --allow-empty -m "second commit";
+            git tag release-1.1.0;
+        )?;
+
+        // Act
+        let tag = repo.get_latest_tag()?;
+
+        // Assert
+        assert_that!(tag.version).is_equal_to(Version::new(1, 1, 0));
+        assert_that!(tag.to_string()).is_equal_to("v1.1.0".to_string());
+        Ok(())
+    }
+
     #[sealed_test]
     fn get_latest_package_tag() -> Result<()> {
         // Arrange
@@ -579,7 +844,122 @@ mod test {
         let tag = repo.get_latest_package_tag("lunatic-timer-api")?;
 
         // Assert
-        assert_that!(tag.to_string()).is_equal_to(&"lunatic-timer-api-v0.12.0".to_string());
+        assert_that!(tag.to_string()).is_equal_to("lunatic-timer-api-v0.12.0".to_string());
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn get_latest_package_tag_with_tag_format() -> Result<()> {
+        // Arrange
+        let mut packages = HashMap::new();
+        packages.insert(
+            "core".to_string(),
+            MonoRepoPackage {
+                path: PathBuf::from("core"),
+                ..Default::default()
+            },
+        );
+        packages.insert(
+            "@scope/ui".to_string(),
+            MonoRepoPackage {
+                path: PathBuf::from("ui"),
+                tag_format: Some("{{package}}@{{version}}".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let settings = Settings {
+            tag_format: Some("{{package}}/v{{version}}".to_string()),
+            packages,
+            ..Default::default()
+        };
+
+        let repo = Repository::init(".")?;
+        let settings = toml::to_string(&settings)?;
+
+        run_cmd!(
+            echo $settings > cog.toml;
+            git add .;
+            git commit -m "first commit";
+            git tag core/v1.2.3;
+            git tag @scope/ui@0.4.0;
+            git tag 2.0.0;
+        )?;
+
+        // Act
+        let core = repo.get_latest_package_tag("core")?;
+        let ui = repo.get_latest_package_tag("@scope/ui")?;
+        let global = repo.get_latest_tag()?;
+
+        // Assert
+        assert_that!(core.version).is_equal_to(Version::new(1, 2, 3));
+        assert_that!(core.to_string()).is_equal_to("core/v1.2.3".to_string());
+        assert_that!(ui.version).is_equal_to(Version::new(0, 4, 0));
+        assert_that!(ui.to_string()).is_equal_to("@scope/ui@0.4.0".to_string());
+        assert_that!(global.to_string()).is_equal_to("2.0.0".to_string());
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_only_list_tags_matching_package_tag_format() -> Result<()> {
+        // Arrange
+        let mut packages = HashMap::new();
+        packages.insert(
+            "api".to_string(),
+            MonoRepoPackage {
+                path: PathBuf::from("api"),
+                ..Default::default()
+            },
+        );
+
+        let settings = Settings {
+            tag_prefix: Some("v".to_string()),
+            packages,
+            ..Default::default()
+        };
+
+        let repo = Repository::init(".")?;
+        let settings = toml::to_string(&settings)?;
+
+        run_cmd!(
+            echo $settings > cog.toml;
+            git add .;
+            git commit -m "first commit";
+            git tag v1.0.0;
+            git tag api-v0.1.0;
+            git tag 9.0.0-rapid;
+        )?;
+
+        // Act
+        let tags = repo.tags()?;
+        let global = repo.get_latest_tag()?;
+
+        // Assert
+        assert_that!(tags).is_equal_to(vec!["v1.0.0".to_string(), "api-v0.1.0".to_string()]);
+        assert_that!(global.to_string()).is_equal_to("v1.0.0".to_string());
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_reject_tag_not_matching_tag_format() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            tag_format: Some("release/v{{version}}".to_string()),
+            ..Default::default()
+        };
+
+        Repository::init(".")?;
+        let settings = toml::to_string(&settings)?;
+        run_cmd!(echo $settings > cog.toml;)?;
+
+        // Act
+        let tag = Tag::from_str("release/v1.0.0", None)?;
+        let mismatch = Tag::from_str("v1.0.0", None);
+
+        // Assert
+        assert_that!(tag.version).is_equal_to(Version::new(1, 0, 0));
+        assert_that!(tag.to_string()).is_equal_to("release/v1.0.0".to_string());
+        assert_that!(mismatch).is_err();
         Ok(())
     }
 }
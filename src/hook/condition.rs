@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use itertools::Itertools;
+
+use crate::hook::HookContext;
+
+// Variables a condition can refer to
+const VARIABLES: [&str; 4] = ["increment", "version", "latest", "package"];
+
+/// A hook `if` condition: `==` and `!=` comparisons between a variable and a quoted value,
+/// combined with `&&` and `||`, ex: `increment == 'major' || package == 'cli'`
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct HookCondition(Vec<Vec<Comparison>>);
+
+#[derive(Debug, Eq, PartialEq)]
+struct Comparison {
+    variable: String,
+    value: String,
+    equal: bool,
+}
+
+impl HookCondition {
+    pub(crate) fn matches(&self, context: &HookContext) -> bool {
+        self.0.iter().any(|comparisons| {
+            comparisons.iter().all(|comparison| {
+                let value = context.get(&comparison.variable).unwrap_or_default();
+                (value == comparison.value) == comparison.equal
+            })
+        })
+    }
+}
+
+impl FromStr for HookCondition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let disjunction = split_unquoted(s, "||")?
+            .into_iter()
+            .map(|conjunction| {
+                split_unquoted(conjunction, "&&")?
+                    .into_iter()
+                    .map(Comparison::from_str)
+                    .try_collect()
+            })
+            .try_collect()?;
+
+        Ok(HookCondition(disjunction))
+    }
+}
+
+// Split on the given operator, ignoring occurrences inside quoted values
+fn split_unquoted<'a>(s: &'a str, operator: &str) -> Result<Vec<&'a str>> {
+    let mut parts = vec![];
+    let mut quote = None;
+    let mut start = 0;
+
+    for (idx, c) in s.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if idx >= start && s[idx..].starts_with(operator) => {
+                parts.push(&s[start..idx]);
+                start = idx + operator.len();
+            }
+            None => {}
+        }
+    }
+
+    ensure!(
+        quote.is_none(),
+        "unterminated quoted value in `{}`",
+        s.trim()
+    );
+    parts.push(&s[start..]);
+    Ok(parts)
+}
+
+impl FromStr for Comparison {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let equal_parts = split_unquoted(s, "==")?;
+        let not_equal_parts = split_unquoted(s, "!=")?;
+        let (variable, value, equal) = match (equal_parts.as_slice(), not_equal_parts.as_slice()) {
+            ([variable, value], [_]) => (*variable, *value, true),
+            ([_], [variable, value]) => (*variable, *value, false),
+            _ => bail!(
+                "expected a single `==` or `!=` comparison in `{}`",
+                s.trim()
+            ),
+        };
+
+        let variable = variable.trim();
+        ensure!(
+            VARIABLES.contains(&variable),
+            "unknown variable `{variable}`, expected one of {}",
+            VARIABLES.join(", ")
+        );
+
+        let value = value.trim();
+        let value = ['\'', '"']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)?
+                    .strip_suffix(*quote)
+                    .filter(|inner| !inner.contains(*quote))
+            })
+            .ok_or_else(|| anyhow!("value `{value}` must be quoted"))?;
+
+        Ok(Comparison {
+            variable: variable.to_string(),
+            value: value.to_string(),
+            equal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use crate::conventional::version::Increment;
+    use crate::hook::condition::HookCondition;
+    use crate::hook::HookContext;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_match_condition() -> anyhow::Result<()> {
+        let condition =
+            HookCondition::from_str("increment == 'major' || package != \"cli\" && latest == ''")?;
+
+        let major = HookContext {
+            increment: Some(Increment::Major),
+            package: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        let minor = HookContext {
+            increment: Some(Increment::Minor),
+            package: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        assert_that!(condition.matches(&major)).is_true();
+        assert_that!(condition.matches(&minor)).is_false();
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_condition() {
+        assert_that!(HookCondition::from_str("branch == 'main'")).is_err();
+        assert_that!(HookCondition::from_str("increment == major")).is_err();
+        assert_that!(HookCondition::from_str("increment = 'major'")).is_err();
+    }
+
+    #[test]
+    fn should_not_split_operators_inside_quoted_values() -> anyhow::Result<()> {
+        let condition = HookCondition::from_str(
+            "version == \"a||b\" && package != 'x&&y==z' || latest == '!='",
+        )?;
+
+        let matching = HookContext {
+            version: Some("a||b".to_string()),
+            package: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        let not_matching = HookContext {
+            version: Some("a".to_string()),
+            ..Default::default()
+        };
+
+        let latest = HookContext {
+            latest: Some("!=".to_string()),
+            ..Default::default()
+        };
+
+        assert_that!(condition.matches(&matching)).is_true();
+        assert_that!(condition.matches(&not_matching)).is_false();
+        assert_that!(condition.matches(&latest)).is_true();
+        assert_that!(HookCondition::from_str("version == 'a||b")).is_err();
+        Ok(())
+    }
+}
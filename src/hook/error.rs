@@ -1,13 +1,22 @@
-use crate::hook;
+use crate::hook::parser::Rule;
 use serde::de::StdError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub struct HookParseError {
     error: Box<dyn Error + Sync + Send>,
 }
 
+impl HookParseError {
+    pub(crate) fn invalid_date_format(format: &str) -> Self {
+        Self {
+            error: format!("invalid date format `{format}`").into(),
+        }
+    }
+}
+
 impl From<semver::Error> for HookParseError {
     fn from(err: semver::Error) -> Self {
         Self {
@@ -16,14 +25,47 @@ impl From<semver::Error> for HookParseError {
     }
 }
 
-impl From<pest::error::Error<hook::parser::Rule>> for HookParseError {
-    fn from(err: pest::error::Error<hook::parser::Rule>) -> Self {
+impl From<pest::error::Error<Rule>> for HookParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
         Self {
-            error: Box::new(err),
+            error: Box::new(err.renamed_rules(rule_name)),
         }
     }
 }
 
+// Name the expected tokens the way they are written in hooks
+fn rule_name(rule: &Rule) -> String {
+    let name = match rule {
+        Rule::current_version => "`version`",
+        Rule::latest_version => "`latest`",
+        Rule::current_tag => "`version_tag`",
+        Rule::latest_tag => "`latest_tag`",
+        Rule::package => "`package`",
+        Rule::package_path => "`package_path`",
+        Rule::date => "`date`",
+        Rule::date_format => "a date format",
+        Rule::commit_sha => "`commit_sha`",
+        Rule::short_sha => "`short_sha`",
+        Rule::branch => "`branch`",
+        Rule::add => "`+`",
+        Rule::amt => "an amount",
+        Rule::major => "`major`",
+        Rule::minor => "`minor`",
+        Rule::patch => "`patch`",
+        Rule::ops => "an increment, ex: `+1minor`",
+        Rule::component => "a version component, ex: `.major`",
+        Rule::component_name => "`major`, `minor` or `patch`",
+        Rule::pre_release => "a pre-release, ex: `-alpha`",
+        Rule::build_metadata => "build metadata, ex: `+build.1`",
+        Rule::identifier | Rule::identifiers => "an identifier",
+        Rule::version => "a `{{...}}` expression",
+        Rule::EOI => "end of input",
+        _ => return format!("{rule:?}"),
+    };
+
+    name.to_string()
+}
+
 impl Display for HookParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "failed to parse bump hook\n")?;
@@ -32,3 +74,19 @@ impl Display for HookParseError {
 }
 
 impl StdError for HookParseError {}
+
+/// A hook exited unsuccessfully or timed out, along with the end of its output
+#[derive(Debug)]
+pub struct HookFailure {
+    pub(crate) cause: String,
+    pub(crate) output_tail: Vec<String>,
+    pub(crate) log: Option<PathBuf>,
+}
+
+impl Display for HookFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.cause)
+    }
+}
+
+impl StdError for HookFailure {}
@@ -1,16 +1,35 @@
-mod error;
+mod condition;
+pub(crate) mod error;
 mod parser;
+pub(crate) mod run_log;
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
 use std::ops::Range;
-use std::process::Command;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::str::FromStr;
-use std::{fmt, path};
-
-use crate::Tag;
-use parser::Token;
-
-use anyhow::{anyhow, ensure, Result};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use std::{fmt, path, thread};
+
+use crate::conventional::version::Increment;
+use crate::settings::HookSetting;
+use crate::{Tag, SETTINGS};
+use condition::HookCondition;
+use error::HookFailure;
+use parser::{Component, Token};
+use run_log::HookLog;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use chrono::Utc;
+use log::warn;
+
+const DEFAULT_HOOK_SHELL: &str = "sh -c";
+// Output lines of a failed hook shown in the bump error
+const OUTPUT_TAIL_LINES: usize = 20;
+const OUTPUT_GRACE_PERIOD: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct VersionSpan {
@@ -33,7 +52,14 @@ impl VersionSpan {
         &mut self,
         version: Option<&HookVersion>,
         latest: Option<&HookVersion>,
+        context: &HookContext,
     ) -> Result<String> {
+        // `{{version}}` and `{{latest}}` are rendered without tag prefix nor package
+        let bare_version = matches!(
+            self.tokens.front(),
+            Some(Token::Version) | Some(Token::LatestVersion)
+        );
+
         // According to the pest grammar, a `version` or `latest_version` token is expected first
         let mut tag = match self.tokens.pop_front() {
             Some(Token::Version) => version
@@ -57,6 +83,33 @@ impl VersionSpan {
                     .and_then(|version| version.prefixed_tag.package.clone())
                     .ok_or_else(|| anyhow!("Current tag as no {{{{package}}}} info"))
             }
+            Some(Token::PackagePath) => {
+                return context
+                    .package_path
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .ok_or_else(|| anyhow!("No package path to replace {{{{package_path}}}}"))
+            }
+            Some(Token::Date(format)) => return Ok(Utc::now().format(&format).to_string()),
+            Some(Token::CommitSha) => {
+                return context
+                    .commit_sha
+                    .clone()
+                    .ok_or_else(|| anyhow!("No commit found to replace {{{{commit_sha}}}}"))
+            }
+            Some(Token::ShortSha) => {
+                return context
+                    .commit_sha
+                    .as_ref()
+                    .map(|sha| sha.chars().take(7).collect())
+                    .ok_or_else(|| anyhow!("No commit found to replace {{{{short_sha}}}}"))
+            }
+            Some(Token::Branch) => {
+                return context
+                    .branch
+                    .clone()
+                    .ok_or_else(|| anyhow!("No branch found to replace {{{{branch}}}}"))
+            }
 
             _ => unreachable!("Unexpected parsing error"),
         }?;
@@ -83,11 +136,24 @@ impl VersionSpan {
                 // set  build metadata and prerelease
                 Token::PreRelease(pre_release) => tag.version.pre = pre_release,
                 Token::BuildMetadata(build) => tag.version.build = build,
+                // components are always last
+                Token::Component(component) => {
+                    let component = match component {
+                        Component::Major => tag.version.major,
+                        Component::Minor => tag.version.minor,
+                        Component::Patch => tag.version.patch,
+                    };
+                    return Ok(component.to_string());
+                }
                 _ => unreachable!("Unexpected parsing error"),
             }
         }
 
-        Ok(tag.to_string())
+        if bare_version {
+            Ok(Tag::format_version(&tag.version))
+        } else {
+            Ok(tag.to_string())
+        }
     }
 }
 
@@ -102,28 +168,125 @@ impl HookSpan {
         &mut self,
         version: Option<&HookVersion>,
         latest: Option<&HookVersion>,
+        context: &HookContext,
     ) -> Result<String> {
         let mut output = self.content.clone();
         if let Some(mut span) = self.version_spans.pop() {
-            let version_str = span.build_version_str(version, latest)?;
+            let version_str = span.build_version_str(version, latest, context)?;
             let version_str = version_str.as_str();
             output.replace_range(span.range.clone(), version_str);
-            output = parser::parse(&output)?.replace_versions(version, latest)?;
+            output = parser::parse(&output)?.replace_versions(version, latest, context)?;
         }
 
         Ok(output)
     }
 }
 
+/// Values a hook `if` condition can refer to, also exported to the hook
+/// environment as `COG_*` variables
+#[derive(Debug, Default)]
+pub struct HookContext {
+    pub increment: Option<Increment>,
+    pub version: Option<String>,
+    pub latest: Option<String>,
+    pub tag: Option<String>,
+    pub package: Option<String>,
+    pub package_path: Option<PathBuf>,
+    /// `HEAD` when the hook is run
+    pub commit_sha: Option<String>,
+    pub branch: Option<String>,
+    /// The changelog written by the bump
+    pub changelog: Option<PathBuf>,
+}
+
+impl HookContext {
+    fn get(&self, variable: &str) -> Option<String> {
+        match variable {
+            "increment" => self.increment.map(|increment| {
+                match increment {
+                    Increment::Major => "major",
+                    Increment::Minor => "minor",
+                    Increment::Patch => "patch",
+                }
+                .to_string()
+            }),
+            "version" => self.version.clone(),
+            "latest" => self.latest.clone(),
+            "package" => self.package.clone(),
+            _ => None,
+        }
+    }
+
+    fn env_vars(&self) -> [(&'static str, Option<String>); 7] {
+        let path =
+            |path: &Option<PathBuf>| path.as_ref().map(|path| path.to_string_lossy().to_string());
+
+        [
+            ("COG_VERSION", self.version.clone()),
+            ("COG_PREVIOUS_VERSION", self.latest.clone()),
+            ("COG_TAG", self.tag.clone()),
+            ("COG_PACKAGE", self.package.clone()),
+            ("COG_PACKAGE_PATH", path(&self.package_path)),
+            ("COG_INCREMENT", self.get("increment")),
+            ("COG_CHANGELOG", path(&self.changelog)),
+        ]
+    }
+}
+
 #[derive(Debug)]
-pub struct Hook(String);
+pub struct Hook(String, HookOptions);
+
+#[derive(Debug, Default)]
+struct HookOptions {
+    cwd: Option<PathBuf>,
+    env: HashMap<String, String>,
+    condition: Option<HookCondition>,
+    allow_failure: bool,
+    timeout: Option<Duration>,
+    no_shell: bool,
+}
 
 impl FromStr for Hook {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         ensure!(!s.is_empty(), "hook must not be an empty string");
-        Ok(Hook(s.to_string()))
+        Ok(Hook(s.to_string(), HookOptions::default()))
+    }
+}
+
+impl TryFrom<&HookSetting> for Hook {
+    type Error = anyhow::Error;
+
+    fn try_from(setting: &HookSetting) -> Result<Self, Self::Error> {
+        let config = match setting {
+            HookSetting::Command(command) => return command.parse(),
+            HookSetting::Table(config) => config,
+        };
+
+        let condition = config
+            .condition
+            .as_deref()
+            .map(|condition| {
+                condition
+                    .parse()
+                    .with_context(|| format!("invalid `if` condition `{condition}`"))
+            })
+            .transpose()?;
+
+        let timeout = config.timeout.as_deref().map(parse_timeout).transpose()?;
+
+        let mut hook: Hook = config.cmd.parse()?;
+        hook.1 = HookOptions {
+            cwd: config.cwd.clone(),
+            env: config.env.clone(),
+            condition,
+            allow_failure: config.allow_failure,
+            timeout,
+            no_shell: config.no_shell,
+        };
+
+        Ok(hook)
     }
 }
 
@@ -138,36 +301,243 @@ impl Hook {
         &mut self,
         current_version: Option<&HookVersion>,
         next_version: Option<&HookVersion>,
+        context: &HookContext,
     ) -> Result<()> {
         let mut parts = parser::parse(&self.0)?;
-        self.0 = parts.replace_versions(next_version, current_version)?;
+        self.0 = parts.replace_versions(next_version, current_version, context)?;
 
         Ok(())
     }
 
-    pub fn run(&self, package_path: Option<&path::Path>) -> Result<()> {
-        let mut cmd = Command::new("sh");
-        let cmd = cmd.arg("-c").arg(&self.0);
-        if let Some(current_dir) = package_path {
+    /// Whether the hook `if` condition, if any, holds
+    pub(crate) fn is_enabled(&self, context: &HookContext) -> bool {
+        self.1
+            .condition
+            .as_ref()
+            .is_none_or(|condition| condition.matches(context))
+    }
+
+    /// The hook working directory, `cwd` is relative to the package path if any
+    pub(crate) fn current_dir(&self, package_path: Option<&path::Path>) -> Option<PathBuf> {
+        match (package_path, &self.1.cwd) {
+            (Some(package_path), Some(cwd)) => Some(package_path.join(cwd)),
+            (None, Some(cwd)) => Some(cwd.clone()),
+            (package_path, None) => package_path.map(path::Path::to_path_buf),
+        }
+    }
+
+    // Either `hook_shell` followed by the hook, or the hook argument vector
+    fn command(&self) -> Result<Command> {
+        let words = if self.1.no_shell {
+            shell_words::split(&self.0)?
+        } else {
+            let shell = SETTINGS.hook_shell.as_deref().unwrap_or(DEFAULT_HOOK_SHELL);
+            let mut words = shell_words::split(shell)
+                .with_context(|| format!("invalid `hook_shell` `{shell}`"))?;
+            words.push(self.0.clone());
+            words
+        };
+
+        let mut words = words.into_iter();
+        let program = words.next().ok_or_else(|| anyhow!("empty hook command"))?;
+        let mut cmd = Command::new(program);
+        cmd.args(words);
+        Ok(cmd)
+    }
+
+    pub fn run(&self, context: &HookContext) -> Result<()> {
+        self.run_with_log(context, None)
+    }
+
+    /// Run the hook, recording its outcome and output in the given log if any.
+    /// Without a log the hook writes to the terminal directly.
+    pub(crate) fn run_with_log(&self, context: &HookContext, log: Option<&HookLog>) -> Result<()> {
+        let run = self.spawn(context, log.is_some())?;
+
+        if let Some(log) = log {
+            if let Err(err) = log.record(self, &run) {
+                warn!("failed to write hook log {:?}: {err}", log.path());
+            }
+        }
+
+        match run.failure {
+            None => Ok(()),
+            Some(failure) if self.1.allow_failure => {
+                warn!("{failure}, ignored with `allow_failure`");
+                Ok(())
+            }
+            Some(cause) => {
+                let tail = run.output.len().saturating_sub(OUTPUT_TAIL_LINES);
+                Err(HookFailure {
+                    cause,
+                    output_tail: run.output[tail..].to_vec(),
+                    log: log.map(|log| log.path().to_path_buf()),
+                }
+                .into())
+            }
+        }
+    }
+
+    // Spawn the hook, when `capture` is set its output is piped and forwarded to the
+    // terminal while being kept
+    fn spawn(&self, context: &HookContext, capture: bool) -> Result<HookRun> {
+        let mut cmd = self.command()?;
+
+        // Don't leak variables from an enclosing cog process
+        for (name, value) in context.env_vars() {
+            match value {
+                Some(value) => cmd.env(name, value),
+                None => cmd.env_remove(name),
+            };
+        }
+
+        cmd.envs(&self.1.env);
+        if let Some(current_dir) = self.current_dir(context.package_path.as_deref()) {
             cmd.current_dir(current_dir);
         }
-        let status = cmd.status()?;
-        ensure!(status.success(), "hook failed with status {}", status);
-        Ok(())
+
+        if capture {
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        }
+
+        let program = cmd.get_program().to_string_lossy().to_string();
+        let start = Instant::now();
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("failed to spawn `{program}`"))?;
+
+        let output = Arc::new(Mutex::new(vec![]));
+        let readers = [
+            child
+                .stdout
+                .take()
+                .map(|stdout| forward_output(stdout, Arc::clone(&output), false)),
+            child
+                .stderr
+                .take()
+                .map(|stderr| forward_output(stderr, Arc::clone(&output), true)),
+        ];
+
+        let status = match self.1.timeout {
+            None => Some(child.wait()?),
+            Some(timeout) => wait_with_timeout(&mut child, timeout)?,
+        };
+        let duration = start.elapsed();
+
+        // Background processes spawned by the hook may keep the pipes open
+        let deadline = Instant::now() + OUTPUT_GRACE_PERIOD;
+        for reader in readers.iter().flatten() {
+            while !reader.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+
+        let failure = status.map_or_else(
+            || {
+                let timeout = self.1.timeout.unwrap_or_default().as_secs();
+                Some(format!("hook timed out after {timeout}s"))
+            },
+            |status| (!status.success()).then(|| format!("hook failed with status {status}")),
+        );
+
+        let output = std::mem::take(&mut *output.lock().expect("hook output lock"));
+        Ok(HookRun {
+            failure,
+            duration,
+            output,
+        })
+    }
+}
+
+/// The outcome of a hook run
+pub(crate) struct HookRun {
+    failure: Option<String>,
+    duration: Duration,
+    /// stdout and stderr lines, each stream is read by its own thread so the order
+    /// of lines across both streams is not guaranteed
+    output: Vec<String>,
+}
+
+// Echo each line of a hook output stream and keep it
+fn forward_output<R>(stream: R, output: Arc<Mutex<Vec<String>>>, stderr: bool) -> JoinHandle<()>
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut stream = BufReader::new(stream);
+        let mut buf = vec![];
+
+        // Read up to EOF whatever the content, so the hook never writes to a closed pipe
+        while stream.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.trim_end_matches(['\n', '\r']).to_string();
+            buf.clear();
+
+            if stderr {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+
+            output.lock().expect("hook output lock").push(line);
+        }
+    })
+}
+
+// Poll the hook process until it exits, killing it once the timeout elapsed
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if start.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+
+        thread::sleep(Duration::from_millis(50));
     }
 }
 
+// Parse non zero durations like `30s`, `5m` or `1h`, a bare number is a number of seconds
+fn parse_timeout(timeout: &str) -> Result<Duration> {
+    let invalid = || anyhow!("invalid timeout `{timeout}`, expected ex: `30s`, `5m` or `1h`");
+    let unit_idx = timeout
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(timeout.len());
+    let (amount, unit) = timeout.split_at(unit_idx);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+
+    let seconds = match unit.trim() {
+        "" | "s" => Some(amount),
+        "m" => amount.checked_mul(60),
+        "h" => amount.checked_mul(60 * 60),
+        _ => return Err(invalid()),
+    };
+
+    let seconds = seconds.ok_or_else(|| anyhow!("timeout `{timeout}` is too large"))?;
+    ensure!(seconds > 0, "timeout `{timeout}` must be greater than zero");
+
+    Ok(Duration::from_secs(seconds))
+}
+
 #[cfg(test)]
 mod test {
     use cmd_lib::run_cmd;
     use git2::Repository;
     use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
     use std::str::FromStr;
+    use std::time::Duration;
 
     use crate::{Result, Tag};
 
-    use crate::hook::{Hook, HookVersion};
-    use crate::settings::{MonoRepoPackage, Settings};
+    use crate::hook::run_log::HookLog;
+    use crate::hook::{Hook, HookContext, HookVersion};
+    use crate::settings::{HookConfig, HookSetting, MonoRepoPackage, Settings};
     use sealed_test::prelude::*;
     use semver::Version;
     use speculoos::prelude::*;
@@ -202,8 +572,12 @@ mod test {
     #[test]
     fn replace_version_cargo() -> Result<()> {
         let mut hook = Hook::from_str("cargo bump {{version}}")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("cargo bump 1.0.0");
         Ok(())
@@ -217,9 +591,10 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
-        hook.insert_versions(None, Some(&HookVersion::new(tag)))
+        hook.insert_versions(None, Some(&HookVersion::new(tag)), &HookContext::default())
             .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("cargo bump v1.0.0");
@@ -251,9 +626,10 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
-        hook.insert_versions(None, Some(&HookVersion::new(tag)))
+        hook.insert_versions(None, Some(&HookVersion::new(tag)), &HookContext::default())
             .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("echo cog-v1.0.0");
@@ -268,9 +644,10 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
-        hook.insert_versions(Some(&HookVersion::new(tag)), None)
+        hook.insert_versions(Some(&HookVersion::new(tag)), None, &HookContext::default())
             .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("echo v1.0.0");
@@ -280,8 +657,12 @@ mod test {
     #[test]
     fn replace_maven_version() -> Result<()> {
         let mut hook = Hook::from_str("mvn versions:set -DnewVersion={{version}}")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.0.0");
         Ok(())
@@ -290,8 +671,12 @@ mod test {
     #[test]
     fn replace_maven_version_with_expression() -> Result<()> {
         let mut hook = Hook::from_str("mvn versions:set -DnewVersion={{version+1minor-SNAPSHOT}}")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.1.0-SNAPSHOT");
         Ok(())
@@ -306,9 +691,10 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
-        hook.insert_versions(None, Some(&HookVersion::new(tag)))
+        hook.insert_versions(None, Some(&HookVersion::new(tag)), &HookContext::default())
             .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=v1.1.0-SNAPSHOT");
@@ -341,9 +727,10 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
-        hook.insert_versions(None, Some(&HookVersion::new(tag)))
+        hook.insert_versions(None, Some(&HookVersion::new(tag)), &HookContext::default())
             .unwrap();
 
         assert_that!(hook.0.as_str())
@@ -354,8 +741,12 @@ mod test {
     #[test]
     fn leave_hook_untouched_when_no_version() -> Result<()> {
         let mut hook = Hook::from_str("echo \"Hello World\"")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("echo \"Hello World\"");
         Ok(())
@@ -364,8 +755,12 @@ mod test {
     #[test]
     fn replace_quoted_version() -> Result<()> {
         let mut hook = Hook::from_str("echo \"{{version}}\"")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("echo \"1.0.0\"");
         Ok(())
@@ -375,8 +770,12 @@ mod test {
     fn replace_version_with_nested_simple_quoted_arg() -> Result<()> {
         let mut hook =
             Hook::from_str("cog commit chore 'bump snapshot to {{version+1minor-pre}}'")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str()).is_equal_to("cog commit chore 'bump snapshot to 1.1.0-pre'");
         Ok(())
@@ -386,8 +785,12 @@ mod test {
     fn replace_version_with_nested_double_quoted_arg() -> Result<()> {
         let mut hook =
             Hook::from_str("cog commit chore \"bump snapshot to {{version+1minor-pre}}\"")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str())
             .is_equal_to("cog commit chore \"bump snapshot to 1.1.0-pre\"");
@@ -400,6 +803,7 @@ mod test {
         hook.insert_versions(
             Some(&HookVersion::new(Tag::from_str("0.5.9", None)?)),
             Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
         )
         .unwrap();
 
@@ -415,6 +819,7 @@ mod test {
         hook.insert_versions(
             Some(&HookVersion::new(Tag::from_str("0.5.9", None)?)),
             Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
         )
         .unwrap();
 
@@ -426,8 +831,12 @@ mod test {
     fn replace_version_with_pre_and_build_metadata() -> Result<()> {
         let mut hook =
             Hook::from_str("echo \"the latest {{version+1major-pre.alpha-bravo+build.42}}\"")?;
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
         assert_that!(hook.0.as_str())
             .is_equal_to("echo \"the latest 2.0.0-pre.alpha-bravo+build.42\"");
@@ -444,9 +853,10 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
-        hook.insert_versions(None, Some(&HookVersion::new(tag)))
+        hook.insert_versions(None, Some(&HookVersion::new(tag)), &HookContext::default())
             .unwrap();
 
         assert_that!(hook.0.as_str())
@@ -461,16 +871,123 @@ mod test {
 
         let mut hook = Hook::from_str("git commit --allow-empty -m 'chore(snapshot): bump snapshot to {{version+1patch-SNAPSHOT}}'")?;
 
-        hook.insert_versions(None, Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        let outcome = hook.run(None);
+        let outcome = hook.run(&HookContext::default());
 
         assert_that!(outcome).is_ok();
 
         Ok(())
     }
 
+    #[test]
+    fn replace_version_components_and_context() -> Result<()> {
+        let mut hook = Hook::from_str(
+            "echo {{version.major}} {{latest+1minor.minor}} {{short_sha}} {{branch}} {{package_path}}",
+        )?;
+        let context = HookContext {
+            commit_sha: Some("17f7e23081db15e9318aeb37529b1d473cf41cbe".to_string()),
+            branch: Some("main".to_string()),
+            package_path: Some("packages/cog".into()),
+            ..Default::default()
+        };
+
+        hook.insert_versions(
+            Some(&HookVersion::new(Tag::from_str("0.5.9", None)?)),
+            Some(&HookVersion::new(Tag::from_str("1.0.0", None)?)),
+            &context,
+        )?;
+
+        assert_that!(hook.0.as_str()).is_equal_to("echo 1 6 17f7e23 main packages/cog");
+        Ok(())
+    }
+
+    #[test]
+    fn parse_hook_table() -> Result<()> {
+        let setting = HookSetting::Table(HookConfig {
+            cmd: "echo {{version}}".to_string(),
+            timeout: Some("5m".to_string()),
+            ..Default::default()
+        });
+
+        let hook = Hook::try_from(&setting)?;
+
+        assert_that!(hook.0.as_str()).is_equal_to("echo {{version}}");
+        assert_that!(hook.1.timeout).is_equal_to(Some(Duration::from_secs(300)));
+        Ok(())
+    }
+
+    #[test]
+    fn reject_zero_and_overflowing_timeouts() {
+        let hook = |timeout: &str| {
+            Hook::try_from(&HookSetting::Table(HookConfig {
+                cmd: "echo".to_string(),
+                timeout: Some(timeout.to_string()),
+                ..Default::default()
+            }))
+        };
+
+        assert_that!(hook("0")).is_err();
+        assert_that!(hook("0s")).is_err();
+        assert_that!(hook("0h")).is_err();
+        assert_that!(hook(&format!("{}h", u64::MAX / 60))).is_err();
+        assert_that!(hook(&format!("{}m", u64::MAX))).is_err();
+    }
+
+    #[sealed_test]
+    fn kill_hook_on_timeout() -> Result<()> {
+        let setting = |allow_failure| {
+            HookSetting::Table(HookConfig {
+                cmd: "sleep 10".to_string(),
+                timeout: Some("1s".to_string()),
+                allow_failure,
+                ..Default::default()
+            })
+        };
+
+        let outcome = Hook::try_from(&setting(false))?.run(&HookContext::default());
+        assert_that!(outcome)
+            .is_err()
+            .matches(|err| err.to_string() == "hook timed out after 1s");
+
+        let outcome = Hook::try_from(&setting(true))?.run(&HookContext::default());
+        assert_that!(outcome).is_ok();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn capture_non_utf8_hook_output() -> Result<()> {
+        let hook = Hook::from_str(r"printf '\377\n'; head -c 300000 /dev/zero | tr '\0' a; echo")?;
+        let log = HookLog::new(PathBuf::from("hooks.log"));
+
+        hook.run_with_log(&HookContext::default(), Some(&log))?;
+
+        let log = std::fs::read_to_string("hooks.log")?;
+        assert_that!(log.as_str()).contains("    \u{FFFD}\n");
+        assert_that!(log.as_str()).ends_with(format!("    {}\n", "a".repeat(300000)));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn run_hook_without_shell() -> Result<()> {
+        let setting = HookSetting::Table(HookConfig {
+            cmd: "touch 'a file' $HOME".to_string(),
+            no_shell: true,
+            ..Default::default()
+        });
+
+        Hook::try_from(&setting)?.run(&HookContext::default())?;
+
+        assert_that!(Path::new("a file")).exists();
+        assert_that!(Path::new("$HOME")).exists();
+        Ok(())
+    }
+
     #[sealed_test]
     fn replace_package_name_and_version_tag_with_expression() -> Result<()> {
         let mut packages = HashMap::new();
@@ -498,6 +1015,7 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            raw: None,
         };
 
         let tag = Tag {
@@ -505,11 +1023,13 @@ mod test {
             prefix: Some("v".to_string()),
             version: Version::new(1, 1, 0),
             oid: None,
+            raw: None,
         };
 
         hook.insert_versions(
             Some(&HookVersion::new(current)),
             Some(&HookVersion::new(tag)),
+            &HookContext::default(),
         )
         .unwrap();
 
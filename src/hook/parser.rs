@@ -3,6 +3,7 @@ use std::collections::VecDeque;
 use crate::hook::{HookSpan, VersionSpan};
 
 use crate::hook::error::HookParseError;
+use chrono::format::{Item, StrftimeItems};
 use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use pest_derive::Parser as ParserDerive;
@@ -27,8 +28,25 @@ pub enum Token {
     Patch,
     PreRelease(Prerelease),
     BuildMetadata(BuildMetadata),
+    Component(Component),
+    /// The current date in UTC, ex: `{{date:%Y%m%d}}`, formatted as `%Y-%m-%d` by default
+    Date(String),
+    CommitSha,
+    ShortSha,
+    PackagePath,
+    Branch,
 }
 
+/// A version component accessor, ex: `{{version.major}}`
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Component {
+    Major,
+    Minor,
+    Patch,
+}
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
 pub fn parse(hook: &str) -> Result<HookSpan, HookParseError> {
     let pairs = HookDslParser::parse(Rule::version_dsl, hook)?
         .next()
@@ -62,6 +80,31 @@ fn parse_version(pair: Pair<Rule>) -> Result<VersionSpan, HookParseError> {
             Rule::latest_version => tokens.push_back(Token::LatestVersion),
             Rule::latest_tag => tokens.push_back(Token::LatestVersionTag),
             Rule::package => tokens.push_back(Token::Package),
+            Rule::package_path => tokens.push_back(Token::PackagePath),
+            Rule::commit_sha => tokens.push_back(Token::CommitSha),
+            Rule::short_sha => tokens.push_back(Token::ShortSha),
+            Rule::branch => tokens.push_back(Token::Branch),
+            Rule::date => {
+                let format = pair
+                    .into_inner()
+                    .next()
+                    .map_or(DEFAULT_DATE_FORMAT, |format| format.as_str());
+
+                // Invalid specifiers would only fail when rendering the date
+                if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                    return Err(HookParseError::invalid_date_format(format));
+                }
+
+                tokens.push_back(Token::Date(format.to_string()));
+            }
+            Rule::component => {
+                let component = match pair.into_inner().as_str() {
+                    "major" => Component::Major,
+                    "minor" => Component::Minor,
+                    _ => Component::Patch,
+                };
+                tokens.push_back(Token::Component(component));
+            }
             Rule::ops => parse_operator(&mut tokens, pair.into_inner())?,
             Rule::pre_release => {
                 let identifiers = pair.into_inner().next().unwrap();
@@ -105,7 +148,7 @@ fn parse_operator(
 mod test {
     use std::collections::VecDeque;
 
-    use crate::hook::parser::Token;
+    use crate::hook::parser::{Component, Token};
     use crate::hook::{parser, VersionSpan};
 
     use semver::Prerelease;
@@ -180,6 +223,60 @@ mod test {
             });
     }
 
+    #[test]
+    fn parse_version_component() {
+        let result = parser::parse("major: {{version+1major.major}}");
+        assert_that!(result)
+            .is_ok()
+            .map(|span| &span.version_spans)
+            .contains(&VersionSpan {
+                range: 7..31,
+                tokens: VecDeque::from(vec![
+                    Token::Version,
+                    Token::Add,
+                    Token::Amount(1),
+                    Token::Major,
+                    Token::Component(Component::Major),
+                ]),
+            });
+    }
+
+    #[test]
+    fn parse_date_and_commit_info() -> anyhow::Result<()> {
+        let span = parser::parse("{{date:%d/%m}} {{date}} {{short_sha}} {{branch}}")?;
+
+        assert_that!(&span.version_spans).contains(&VersionSpan {
+            range: 0..14,
+            tokens: VecDeque::from(vec![Token::Date("%d/%m".to_string())]),
+        });
+
+        assert_that!(&span.version_spans).contains(&VersionSpan {
+            range: 15..23,
+            tokens: VecDeque::from(vec![Token::Date("%Y-%m-%d".to_string())]),
+        });
+
+        assert_that!(&span.version_spans).contains(&VersionSpan {
+            range: 24..37,
+            tokens: VecDeque::from(vec![Token::ShortSha]),
+        });
+
+        assert_that!(&span.version_spans).contains(&VersionSpan {
+            range: 38..48,
+            tokens: VecDeque::from(vec![Token::Branch]),
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_date_format_is_err() {
+        let result = parser::parse("{{date:%Q}}");
+
+        assert_that!(result)
+            .is_err()
+            .matches(|err| err.to_string().contains("invalid date format `%Q`"));
+    }
+
     #[test]
     fn invalid_dsl_is_err() {
         let result = parser::parse("the greatest {{+patch-pre.alpha0}}");
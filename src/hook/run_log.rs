@@ -0,0 +1,48 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::hook::{Hook, HookRun};
+
+/// Append only log of the hooks run, with their outcome, duration and output
+pub(crate) struct HookLog {
+    path: PathBuf,
+}
+
+impl HookLog {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        HookLog { path }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn record(&self, hook: &Hook, run: &HookRun) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let outcome = run.failure.as_deref().unwrap_or("hook succeeded");
+        writeln!(
+            file,
+            "[{}] `{hook}`: {outcome} in {:.2}s",
+            Utc::now().to_rfc3339(),
+            run.duration.as_secs_f64()
+        )?;
+
+        for line in &run.output {
+            writeln!(file, "    {line}")?;
+        }
+
+        Ok(())
+    }
+}
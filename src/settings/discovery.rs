@@ -0,0 +1,339 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::git::repository::Repository;
+use crate::settings::error::SettingError;
+use crate::settings::MonoRepoPackage;
+use crate::CONFIG_PATH;
+
+// Directories never scanned for packages, even when not git ignored
+const IGNORED_DIRS: [&str; 2] = ["node_modules", "target"];
+
+// Characters starting a glob expression in a path component
+const GLOB_META_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+/// Where to look for monorepo packages when populating `packages`
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PackagesDiscovery {
+    /// Members of the Cargo workspace declared in the root `Cargo.toml`
+    Cargo,
+    /// npm or yarn workspaces declared in `package.json`, or pnpm workspaces
+    /// declared in `pnpm-workspace.yaml`
+    Npm,
+    /// Every directory matching the given pattern, named after the directory
+    Glob(String),
+}
+
+impl PackagesDiscovery {
+    /// Find packages relative to the repository root, keyed by package name
+    pub(crate) fn discover(
+        &self,
+        root: &Path,
+        repository: &Repository,
+    ) -> Result<HashMap<String, MonoRepoPackage>, SettingError> {
+        match self {
+            PackagesDiscovery::Cargo => discover_cargo(root, repository),
+            PackagesDiscovery::Npm => discover_npm(root, repository),
+            PackagesDiscovery::Glob(pattern) => {
+                let dirs = matching_dirs(root, repository, &[pattern.to_string()], &[])
+                    .map_err(|cause| discovery_error(&root.join(CONFIG_PATH), cause))?;
+
+                Ok(dirs
+                    .into_iter()
+                    .filter_map(|dir| {
+                        let name = dir.file_name()?.to_string_lossy().to_string();
+                        Some((name, package(dir)))
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+fn discover_cargo(
+    root: &Path,
+    repository: &Repository,
+) -> Result<HashMap<String, MonoRepoPackage>, SettingError> {
+    let manifest = root.join("Cargo.toml");
+    let cargo_toml: toml::Value = read_manifest(&manifest, |content| {
+        toml::from_str(content).map_err(|err| err.to_string())
+    })?;
+
+    let workspace = cargo_toml.get("workspace");
+    let members = string_array(workspace.and_then(|workspace| workspace.get("members")));
+    let exclude = string_array(workspace.and_then(|workspace| workspace.get("exclude")));
+    let dirs = matching_dirs(root, repository, &members, &exclude)
+        .map_err(|cause| discovery_error(&manifest, cause))?;
+
+    let mut packages = HashMap::new();
+    for dir in dirs {
+        let member_manifest = root.join(&dir).join("Cargo.toml");
+        if !member_manifest.exists() {
+            continue;
+        }
+
+        let member: toml::Value = read_manifest(&member_manifest, |content| {
+            toml::from_str(content).map_err(|err| err.to_string())
+        })?;
+
+        // Nested virtual manifests have no package name
+        if let Some(name) = member
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(toml::Value::as_str)
+        {
+            packages.insert(name.to_string(), package(dir));
+        }
+    }
+
+    Ok(packages)
+}
+
+fn discover_npm(
+    root: &Path,
+    repository: &Repository,
+) -> Result<HashMap<String, MonoRepoPackage>, SettingError> {
+    let package_json = root.join("package.json");
+    let pnpm_workspace = root.join("pnpm-workspace.yaml");
+
+    let workspaces = if package_json.exists() {
+        let manifest: serde_json::Value = read_manifest(&package_json, |content| {
+            serde_json::from_str(content).map_err(|err| err.to_string())
+        })?;
+
+        // Either `"workspaces": [..]` or yarn's `"workspaces": { "packages": [..] }`
+        let workspaces = &manifest["workspaces"];
+        workspaces
+            .as_array()
+            .or_else(|| workspaces["packages"].as_array())
+            .map(|patterns| {
+                patterns
+                    .iter()
+                    .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        vec![]
+    };
+
+    let (manifest, patterns) = if workspaces.is_empty() && pnpm_workspace.exists() {
+        let patterns = read_manifest(&pnpm_workspace, |content| {
+            Ok(pnpm_workspace_patterns(content))
+        })?;
+        (pnpm_workspace, patterns)
+    } else {
+        (package_json, workspaces)
+    };
+
+    let (exclude, include): (Vec<String>, Vec<String>) = patterns
+        .into_iter()
+        .partition(|pattern| pattern.starts_with('!'));
+    let exclude: Vec<String> = exclude
+        .iter()
+        .map(|pattern| pattern.trim_start_matches('!').to_string())
+        .collect();
+
+    let dirs = matching_dirs(root, repository, &include, &exclude)
+        .map_err(|cause| discovery_error(&manifest, cause))?;
+
+    let mut packages = HashMap::new();
+    for dir in dirs {
+        let member_manifest = root.join(&dir).join("package.json");
+        if !member_manifest.exists() {
+            continue;
+        }
+
+        let member: serde_json::Value = read_manifest(&member_manifest, |content| {
+            serde_json::from_str(content).map_err(|err| err.to_string())
+        })?;
+
+        if let Some(name) = member["name"].as_str() {
+            packages.insert(name.to_string(), package(dir));
+        }
+    }
+
+    Ok(packages)
+}
+
+// Read the `packages` list of a pnpm workspace file
+fn pnpm_workspace_patterns(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .skip_while(|line| line.trim_end() != "packages:")
+        .skip(1)
+        .take_while(|line| {
+            line.trim().is_empty() || line.starts_with(char::is_whitespace) || line.starts_with('-')
+        })
+        .filter_map(|line| line.trim().strip_prefix('-'))
+        .map(|pattern| pattern.trim().trim_matches(['\'', '"']).to_string())
+        .collect()
+}
+
+// Collect the directories matching any of the `include` patterns and none of the
+// `exclude` ones, relative to the root. Only the literal base of each pattern is
+// walked, no deeper than the pattern goes, skipping hidden and git ignored directories.
+fn matching_dirs(
+    root: &Path,
+    repository: &Repository,
+    include: &[String],
+    exclude: &[String],
+) -> Result<Vec<PathBuf>, String> {
+    let exclude = glob_set(exclude)?;
+
+    let mut dirs = BTreeSet::new();
+    for pattern in include {
+        let matcher = glob_set(std::slice::from_ref(pattern))?;
+        let (base, max_depth) = pattern_base(pattern);
+        if !root.join(&base).is_dir() {
+            continue;
+        }
+
+        let mut to_visit = vec![base];
+        while let Some(dir) = to_visit.pop() {
+            let depth = dir.components().count();
+            if depth > 0 && matcher.is_match(&dir) && !exclude.is_match(&dir) {
+                dirs.insert(dir.clone());
+            }
+
+            if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                continue;
+            }
+
+            let entries = fs::read_dir(root.join(&dir)).map_err(|err| err.to_string())?;
+            for entry in entries {
+                let entry = entry.map_err(|err| err.to_string())?;
+                if !entry.file_type().map_err(|err| err.to_string())?.is_dir() {
+                    continue;
+                }
+
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') || IGNORED_DIRS.contains(&name.as_str()) {
+                    continue;
+                }
+
+                let path = dir.join(name);
+                if !repository.is_path_ignored(&path) {
+                    to_visit.push(path);
+                }
+            }
+        }
+    }
+
+    Ok(dirs.into_iter().collect())
+}
+
+// The directory a pattern starts matching in and how many components deep its
+// matches go, `None` when a `**` component matches any depth
+fn pattern_base(pattern: &str) -> (PathBuf, Option<usize>) {
+    let pattern = normalize_pattern(pattern);
+    let components: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+    let base = components
+        .iter()
+        .take_while(|component| !component.contains(GLOB_META_CHARS))
+        .collect();
+    let max_depth = if components.iter().any(|component| component.contains("**")) {
+        None
+    } else {
+        Some(components.len())
+    };
+
+    (base, max_depth)
+}
+
+fn normalize_pattern(pattern: &str) -> &str {
+    pattern.trim_start_matches("./").trim_end_matches('/')
+}
+
+fn glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(normalize_pattern(pattern))
+            .literal_separator(true)
+            .build()
+            .map_err(|err| err.to_string())?;
+        builder.add(glob);
+    }
+
+    builder.build().map_err(|err| err.to_string())
+}
+
+fn read_manifest<T, F>(manifest: &Path, parse: F) -> Result<T, SettingError>
+where
+    F: FnOnce(&str) -> Result<T, String>,
+{
+    let content =
+        fs::read_to_string(manifest).map_err(|err| discovery_error(manifest, err.to_string()))?;
+    parse(&content).map_err(|cause| discovery_error(manifest, cause))
+}
+
+fn string_array(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(toml::Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn package(path: PathBuf) -> MonoRepoPackage {
+    MonoRepoPackage {
+        path,
+        ..Default::default()
+    }
+}
+
+fn discovery_error(manifest: &Path, cause: String) -> SettingError {
+    SettingError::PackageDiscovery {
+        manifest: manifest.to_path_buf(),
+        cause,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::settings::discovery::{pattern_base, pnpm_workspace_patterns};
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_read_pnpm_workspace_patterns() {
+        let content = indoc!(
+            "
+            packages:
+              - 'packages/*'
+              - \"apps/*\"
+              - '!**/test/**'
+            catalog:
+              react: ^18
+            "
+        );
+
+        let patterns = pnpm_workspace_patterns(content);
+
+        assert_that!(patterns).is_equal_to(vec![
+            "packages/*".to_string(),
+            "apps/*".to_string(),
+            "!**/test/**".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn should_walk_patterns_from_their_literal_base() {
+        assert_that!(pattern_base("./crates/*")).is_equal_to((PathBuf::from("crates"), Some(2)));
+        assert_that!(pattern_base("apps/web/")).is_equal_to((PathBuf::from("apps/web"), Some(2)));
+        assert_that!(pattern_base("packages/**/lib-*"))
+            .is_equal_to((PathBuf::from("packages"), None));
+        assert_that!(pattern_base("*")).is_equal_to((PathBuf::new(), Some(1)));
+    }
+}
@@ -1,20 +1,48 @@
 use config::ConfigError;
 use serde::de::StdError;
 use std::fmt::{Debug, Display, Formatter};
+use std::path::PathBuf;
 
 #[derive(Debug)]
-pub(crate) struct SettingError(config::ConfigError);
+pub(crate) enum SettingError {
+    Config(ConfigError),
+    PackageDiscovery {
+        manifest: PathBuf,
+        cause: String,
+    },
+    InvalidPackagePattern {
+        package: String,
+        pattern: String,
+        cause: String,
+    },
+}
 
 impl Display for SettingError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "failed to parse config\n")?;
-        writeln!(f, "\tcause {}", self.0)
+        match self {
+            SettingError::Config(err) => {
+                writeln!(f, "failed to parse config\n")?;
+                writeln!(f, "\tcause {}", err)
+            }
+            SettingError::PackageDiscovery { manifest, cause } => {
+                writeln!(f, "failed to discover packages from {:?}\n", manifest)?;
+                writeln!(f, "\tcause {}", cause)
+            }
+            SettingError::InvalidPackagePattern {
+                package,
+                pattern,
+                cause,
+            } => {
+                writeln!(f, "invalid pattern `{}` in package {}\n", pattern, package)?;
+                writeln!(f, "\tcause {}", cause)
+            }
+        }
     }
 }
 
 impl From<ConfigError> for SettingError {
     fn from(err: ConfigError) -> Self {
-        SettingError(err)
+        SettingError::Config(err)
     }
 }
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::conventional::commit::CommitConfig;
+use crate::conventional::version::{CalVerFormat, Increment, VersionScheme};
 use crate::git::repository::Repository;
 use crate::{CommitsMetadata, CONFIG_PATH, SETTINGS};
 
@@ -11,18 +12,27 @@ use crate::git::hook::Hooks;
 use crate::settings::error::SettingError;
 use config::{Config, File};
 use conventional_commit_parser::commit::CommitType;
-use serde::{Deserialize, Serialize};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
 use std::path::Path;
 
 type CommitsMetadataSettings = HashMap<String, CommitConfig>;
 pub(crate) type AuthorSettings = Vec<AuthorSetting>;
 
+mod discovery;
 mod error;
 
+pub use discovery::PackagesDiscovery;
+
 #[derive(Copy, Clone)]
 pub enum HookType {
     PreBump,
     PostBump,
+    PreCommit,
+    PostChangelog,
+    BumpFailure,
 }
 
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq)]
@@ -32,16 +42,42 @@ pub struct Settings {
     pub ignore_merge_commits: bool,
     pub generate_mono_repository_global_tag: bool,
     pub monorepo_version_separator: Option<String>,
+    pub packages_discovery: Option<PackagesDiscovery>,
     pub branch_whitelist: Vec<String>,
     pub tag_prefix: Option<String>,
-    pub pre_bump_hooks: Vec<String>,
-    pub post_bump_hooks: Vec<String>,
-    pub pre_package_bump_hooks: Vec<String>,
-    pub post_package_bump_hooks: Vec<String>,
+    /// Tag name template, ex: `v{{version}}` or `{{package}}/v{{version}}`
+    pub tag_format: Option<String>,
+    pub legacy_tag_prefixes: Vec<String>,
+    pub reachable_tags_only: bool,
+    pub version_scheme: VersionScheme,
+    pub calver_format: CalVerFormat,
+    /// Shell command hooks are appended to, ex: `bash -euo pipefail -c`, defaults to `sh -c`
+    pub hook_shell: Option<String>,
+    /// Where hooks output and timing is logged, relative to the repository root, defaults to
+    /// `.git/cog/hooks.log`. Hooks output is piped to be captured, so hooks don't see a terminal,
+    /// set it to `""` to disable the capture.
+    pub hook_log: Option<PathBuf>,
+    pub pre_bump_hooks: Vec<HookSetting>,
+    pub post_bump_hooks: Vec<HookSetting>,
+    pub pre_package_bump_hooks: Vec<HookSetting>,
+    pub post_package_bump_hooks: Vec<HookSetting>,
+    /// Run by `cog commit` before committing
+    pub pre_commit_hooks: Vec<HookSetting>,
+    /// Run after a changelog is written, before the version commit
+    pub post_changelog_hooks: Vec<HookSetting>,
+    /// Run after a package changelog is written, before the version commit
+    pub post_changelog_package_hooks: Vec<HookSetting>,
+    /// Run when a bump fails and its changes are stashed
+    pub on_bump_failure_hooks: Vec<HookSetting>,
+    /// Run when a package bump fails and its changes are stashed
+    pub on_bump_failure_package_hooks: Vec<HookSetting>,
     pub commit_types: CommitsMetadataSettings,
     pub changelog: Changelog,
+    pub bump: BumpSettings,
+    pub monorepo: MonoRepoSettings,
     pub bump_profiles: HashMap<String, BumpProfile>,
     pub packages: HashMap<String, MonoRepoPackage>,
+    pub branches: HashMap<String, BranchSettings>,
 }
 
 impl Default for Settings {
@@ -51,16 +87,32 @@ impl Default for Settings {
             ignore_merge_commits: false,
             generate_mono_repository_global_tag: true,
             monorepo_version_separator: None,
+            packages_discovery: None,
             branch_whitelist: vec![],
             tag_prefix: None,
+            tag_format: None,
+            legacy_tag_prefixes: vec![],
+            reachable_tags_only: false,
+            version_scheme: VersionScheme::default(),
+            calver_format: CalVerFormat::default(),
+            hook_shell: None,
+            hook_log: None,
             pre_bump_hooks: vec![],
             post_bump_hooks: vec![],
             pre_package_bump_hooks: vec![],
             post_package_bump_hooks: vec![],
+            pre_commit_hooks: vec![],
+            post_changelog_hooks: vec![],
+            post_changelog_package_hooks: vec![],
+            on_bump_failure_hooks: vec![],
+            on_bump_failure_package_hooks: vec![],
             commit_types: Default::default(),
             changelog: Default::default(),
+            bump: Default::default(),
+            monorepo: Default::default(),
             bump_profiles: Default::default(),
             packages: Default::default(),
+            branches: Default::default(),
         }
     }
 }
@@ -71,15 +123,41 @@ pub struct MonoRepoPackage {
     /// The package path, relative to the repository root dir.
     /// Used to scan commits and set hook commands current directory
     pub path: PathBuf,
+    /// Additional glob patterns, relative to the repository root, of files belonging to the package
+    pub include: Vec<String>,
+    /// Glob patterns, relative to the repository root, of files ignored by the package
+    pub exclude: Vec<String>,
     /// Where to write the changelog
     pub changelog_path: Option<String>,
+    /// Overrides `tag_format` for this package, ex: `@scope/pkg@{{version}}`
+    pub tag_format: Option<String>,
+    /// Overrides `changelog.package_template` for this package
+    pub template: Option<String>,
+    /// Overrides `changelog.remote` for this package
+    pub remote: Option<String>,
+    /// Overrides `changelog.owner` for this package
+    pub owner: Option<String>,
+    /// Overrides `changelog.repository` for this package
+    pub repository: Option<String>,
     /// Bumping package marked as public api will increment
     /// the global monorepo version when using `cog bump --auto`
     pub public_api: bool,
     /// Overrides `pre_package_bump_hooks`
-    pub pre_bump_hooks: Option<Vec<String>>,
+    pub pre_bump_hooks: Option<Vec<HookSetting>>,
     /// Overrides `post_package_bump_hooks`
-    pub post_bump_hooks: Option<Vec<String>>,
+    pub post_bump_hooks: Option<Vec<HookSetting>>,
+    /// Replaces `pre_commit_hooks` for commits scoped with the package name, a commit only
+    /// runs one of them
+    pub pre_commit_hooks: Option<Vec<HookSetting>>,
+    /// Overrides `post_changelog_package_hooks`
+    pub post_changelog_hooks: Option<Vec<HookSetting>>,
+    /// Overrides `on_bump_failure_package_hooks`
+    pub on_bump_failure_hooks: Option<Vec<HookSetting>>,
+    /// Packages this package depends on, bumping one of them bumps this package too
+    pub depends_on: Vec<String>,
+    /// Commit types merged over the global `commit_types` when bumping
+    /// this package and rendering its changelog
+    pub commit_types: CommitsMetadataSettings,
     /// Custom profile to override `pre_bump_hooks`, `post_bump_hooks`
     pub bump_profiles: HashMap<String, BumpProfile>,
 }
@@ -88,11 +166,23 @@ impl Default for MonoRepoPackage {
     fn default() -> Self {
         Self {
             path: Default::default(),
+            include: vec![],
+            exclude: vec![],
             changelog_path: None,
+            tag_format: None,
+            template: None,
+            remote: None,
+            owner: None,
+            repository: None,
             pre_bump_hooks: None,
             post_bump_hooks: None,
+            pre_commit_hooks: None,
+            post_changelog_hooks: None,
+            on_bump_failure_hooks: None,
             bump_profiles: Default::default(),
             public_api: true,
+            depends_on: vec![],
+            commit_types: Default::default(),
         }
     }
 }
@@ -104,6 +194,94 @@ impl MonoRepoPackage {
             .map(PathBuf::from)
             .unwrap_or_else(|| self.path.join("CHANGELOG.md"))
     }
+
+    /// Build a matcher telling which changed files belong to this package
+    pub(crate) fn file_matcher(&self) -> PackageFileMatcher<'_> {
+        PackageFileMatcher {
+            path: &self.path,
+            include: glob_set(&self.include),
+            exclude: glob_set(&self.exclude),
+        }
+    }
+}
+
+/// Match files under the package path or its `include` patterns, minus its `exclude` patterns
+pub(crate) struct PackageFileMatcher<'a> {
+    path: &'a Path,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl PackageFileMatcher<'_> {
+    pub(crate) fn is_match(&self, file: &Path) -> bool {
+        (file.starts_with(self.path) || self.include.is_match(file)) && !self.exclude.is_match(file)
+    }
+}
+
+// Package patterns are checked when loading the settings
+fn glob_set(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for glob in patterns
+        .iter()
+        .filter_map(|pattern| Glob::new(pattern).ok())
+    {
+        builder.add(glob);
+    }
+
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct BumpSettings {
+    /// What to do when an auto bump finds no feature, bug fix or breaking change commit
+    pub no_bump_commits: NoBumpCommits,
+    /// Build metadata template appended to created versions, ex: `{{short_sha}}.{{date}}`
+    pub build_metadata: Option<String>,
+    /// Minimum increment applied to packages whose dependencies were bumped, defaults to `patch`
+    pub dependency_increment: Option<Increment>,
+}
+
+/// Policy applied when no commit requires a version bump
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NoBumpCommits {
+    /// Fail the bump
+    #[default]
+    Error,
+    /// Do nothing and exit successfully
+    Skip,
+    /// Create a patch release anyway
+    Patch,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct MonoRepoSettings {
+    /// Whether packages are versioned on their own or all share the global version
+    pub versioning: MonoRepoVersioning,
+}
+
+/// How package versions relate to each other in a monorepo
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MonoRepoVersioning {
+    /// Each package is bumped from its own commits
+    #[default]
+    Independent,
+    /// Every package is released with the global version computed from all commits
+    Fixed,
+}
+
+/// Release channel configuration for the branches matching a `[branches]` key
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct BranchSettings {
+    /// Pre-release identifier of the versions created on this branch, ex: `beta`
+    /// will produce `2.0.0-beta.1`, `2.0.0-beta.2` etc.
+    pub prerelease: Option<String>,
+    /// SemVer requirement the versions created on this branch must satisfy, ex: `1.x`
+    pub range: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
@@ -152,13 +330,86 @@ pub fn changelog_path() -> &'static PathBuf {
     &SETTINGS.changelog.path
 }
 
+/// A hook command, either a plain string run as is or a table with execution options
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum HookSetting {
+    Command(String),
+    Table(HookConfig),
+}
+
+// Not derived with `untagged`, so table errors name the faulty field
+impl<'de> Deserialize<'de> for HookSetting {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct HookSettingVisitor;
+
+        impl<'de> Visitor<'de> for HookSettingVisitor {
+            type Value = HookSetting;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a hook command string or a hook table")
+            }
+
+            fn visit_str<E>(self, command: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(HookSetting::Command(command.to_string()))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                HookConfig::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(HookSetting::Table)
+            }
+        }
+
+        deserializer.deserialize_any(HookSettingVisitor)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    /// The command to run, supports the version DSL like string hooks
+    pub cmd: String,
+    /// Working directory, relative to the package path for package hooks
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    /// Additional environment variables
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Run the hook only when the condition holds, ex: `increment == 'major'`
+    #[serde(default, rename = "if")]
+    pub condition: Option<String>,
+    /// Log a warning instead of aborting the bump when the hook fails
+    #[serde(default)]
+    pub allow_failure: bool,
+    /// Kill the hook after the given duration, ex: `30s`, `5m` or `1h`
+    #[serde(default)]
+    pub timeout: Option<String>,
+    /// Split `cmd` into an argument vector with shell quoting rules and run it without
+    /// `hook_shell`
+    #[serde(default)]
+    pub no_shell: bool,
+}
+
 #[derive(Debug, Deserialize, Serialize, Default, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct BumpProfile {
     #[serde(default)]
-    pub pre_bump_hooks: Vec<String>,
+    pub pre_bump_hooks: Vec<HookSetting>,
+    #[serde(default)]
+    pub post_bump_hooks: Vec<HookSetting>,
+    #[serde(default)]
+    pub post_changelog_hooks: Vec<HookSetting>,
     #[serde(default)]
-    pub post_bump_hooks: Vec<String>,
+    pub on_bump_failure_hooks: Vec<HookSetting>,
 }
 
 impl Settings {
@@ -168,12 +419,16 @@ impl Settings {
             Some(repo_path) => {
                 let settings_path = repo_path.join(CONFIG_PATH);
                 if settings_path.exists() {
-                    Config::builder()
+                    let mut settings: Settings = Config::builder()
                         .add_source(File::from(settings_path))
                         .build()
                         .map_err(SettingError::from)?
                         .try_deserialize()
-                        .map_err(SettingError::from)
+                        .map_err(SettingError::from)?;
+
+                    settings.discover_packages(repo_path, repository)?;
+                    settings.check_package_patterns()?;
+                    Ok(settings)
                 } else {
                     Ok(Settings::default())
                 }
@@ -182,6 +437,47 @@ impl Settings {
         }
     }
 
+    // Populate packages from workspace manifests, explicit `[packages.x]` entries
+    // take precedence and inherit the discovered path when they don't set one
+    fn discover_packages(
+        &mut self,
+        repo_path: &Path,
+        repository: &Repository,
+    ) -> Result<(), SettingError> {
+        let mut packages = match &self.packages_discovery {
+            Some(discovery) => discovery.discover(repo_path, repository)?,
+            None => return Ok(()),
+        };
+
+        for (name, mut package) in std::mem::take(&mut self.packages) {
+            if let Some(discovered) = packages.remove(&name) {
+                if package.path.as_os_str().is_empty() {
+                    package.path = discovered.path;
+                }
+            }
+
+            packages.insert(name, package);
+        }
+
+        self.packages = packages;
+        Ok(())
+    }
+
+    // Fail on invalid `include` and `exclude` patterns, they would otherwise never match
+    fn check_package_patterns(&self) -> Result<(), SettingError> {
+        for (name, package) in &self.packages {
+            for pattern in package.include.iter().chain(&package.exclude) {
+                Glob::new(pattern).map_err(|err| SettingError::InvalidPackagePattern {
+                    package: name.to_string(),
+                    pattern: pattern.to_string(),
+                    cause: err.kind().to_string(),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn commit_types(&self) -> CommitsMetadata {
         let commit_settings = self.commit_types.clone();
         let mut custom_types = HashMap::new();
@@ -197,6 +493,39 @@ impl Settings {
         default_types
     }
 
+    /// The global commit types overridden with the package ones
+    pub fn package_commit_types(&self, package: &str) -> CommitsMetadata {
+        let mut commit_types = self.commit_types();
+
+        if let Some(package) = self.packages.get(package) {
+            for (key, config) in &package.commit_types {
+                let commit_type = CommitType::from(key.as_str());
+                let mut config = config.clone();
+                // Package types without bump rule inherit the global one
+                if config.bump.is_none() {
+                    config.bump = commit_types
+                        .get(&commit_type)
+                        .and_then(|global| global.bump);
+                }
+
+                commit_types.insert(commit_type, config);
+            }
+        }
+
+        commit_types
+    }
+
+    /// Whether a commit type is declared globally or by any package
+    pub(crate) fn is_commit_type_allowed(&self, commit_type: &CommitType) -> bool {
+        self.commit_types().contains_key(commit_type)
+            || self.packages.values().any(|package| {
+                package
+                    .commit_types
+                    .keys()
+                    .any(|key| &CommitType::from(key.as_str()) == commit_type)
+            })
+    }
+
     fn default_commit_config() -> CommitsMetadata {
         let mut default_types = HashMap::new();
         default_types.insert(CommitType::Feature, CommitConfig::new("Features"));
@@ -236,12 +565,35 @@ impl Settings {
         Template::from_arg(template, context)
     }
 
-    pub fn get_package_changelog_template(&self) -> Result<Template, ChangelogError> {
-        let context = self.get_template_context();
+    /// The remote context of a package, each field falls back to the global changelog one
+    pub fn get_package_template_context(&self, package: &str) -> Option<RemoteContext> {
+        let package = self.packages.get(package);
+
+        let remote = package
+            .and_then(|package| package.remote.as_ref())
+            .or(self.changelog.remote.as_ref());
+
+        let repository = package
+            .and_then(|package| package.repository.as_ref())
+            .or(self.changelog.repository.as_ref());
+
+        let owner = package
+            .and_then(|package| package.owner.as_ref())
+            .or(self.changelog.owner.as_ref());
+
+        RemoteContext::try_new(remote.cloned(), repository.cloned(), owner.cloned())
+    }
+
+    pub fn get_package_changelog_template(
+        &self,
+        package: &str,
+    ) -> Result<Template, ChangelogError> {
+        let context = self.get_package_template_context(package);
         let template = self
-            .changelog
-            .package_template
-            .as_deref()
+            .packages
+            .get(package)
+            .and_then(|package| package.template.as_deref())
+            .or(self.changelog.package_template.as_deref())
             .unwrap_or("package_default");
 
         let template = match template {
@@ -278,6 +630,42 @@ impl Settings {
         }
     }
 
+    /// Get the tag template of the given package, or of global tags when `package` is `None`.
+    /// The global `tag_format` applies to package tags when it has a `{{package}}` placeholder,
+    /// and to global tags otherwise.
+    pub fn tag_format(&self, package: Option<&str>) -> Option<&str> {
+        let has_package =
+            |format: &&str| format.contains("{{package}}") || format.contains("{{ package }}");
+
+        match package {
+            Some(package) => self
+                .packages
+                .get(package)
+                .and_then(|package| package.tag_format.as_deref())
+                .or(self.tag_format.as_deref().filter(has_package)),
+            None => self
+                .tag_format
+                .as_deref()
+                .filter(|format| !has_package(format)),
+        }
+    }
+
+    /// Get the release channel of the given branch, an exact branch name takes
+    /// precedence over glob patterns, then the longest matching pattern wins.
+    pub fn branch_settings(&self, branch: &str) -> Option<&BranchSettings> {
+        self.branches.get(branch).or_else(|| {
+            self.branches
+                .iter()
+                .filter(|(pattern, _)| {
+                    Glob::new(pattern)
+                        .map(|glob| glob.compile_matcher().is_match(branch))
+                        .unwrap_or_default()
+                })
+                .max_by_key(|(pattern, _)| pattern.len())
+                .map(|(_, settings)| settings)
+        })
+    }
+
     pub fn package_paths(&self) -> impl Iterator<Item = &Path> {
         self.packages.values().map(|package| package.path.as_path())
     }
@@ -288,13 +676,25 @@ impl Hooks for Settings {
         &self.bump_profiles
     }
 
-    fn pre_bump_hooks(&self) -> &Vec<String> {
+    fn pre_bump_hooks(&self) -> &Vec<HookSetting> {
         &self.pre_bump_hooks
     }
 
-    fn post_bump_hooks(&self) -> &Vec<String> {
+    fn post_bump_hooks(&self) -> &Vec<HookSetting> {
         &self.post_bump_hooks
     }
+
+    fn pre_commit_hooks(&self) -> &Vec<HookSetting> {
+        &self.pre_commit_hooks
+    }
+
+    fn post_changelog_hooks(&self) -> &Vec<HookSetting> {
+        &self.post_changelog_hooks
+    }
+
+    fn on_bump_failure_hooks(&self) -> &Vec<HookSetting> {
+        &self.on_bump_failure_hooks
+    }
 }
 
 impl Hooks for MonoRepoPackage {
@@ -302,15 +702,33 @@ impl Hooks for MonoRepoPackage {
         &self.bump_profiles
     }
 
-    fn pre_bump_hooks(&self) -> &Vec<String> {
+    fn pre_bump_hooks(&self) -> &Vec<HookSetting> {
         self.pre_bump_hooks
             .as_ref()
             .unwrap_or(&SETTINGS.pre_package_bump_hooks)
     }
 
-    fn post_bump_hooks(&self) -> &Vec<String> {
+    fn post_bump_hooks(&self) -> &Vec<HookSetting> {
         self.post_bump_hooks
             .as_ref()
             .unwrap_or(&SETTINGS.post_package_bump_hooks)
     }
+
+    fn pre_commit_hooks(&self) -> &Vec<HookSetting> {
+        self.pre_commit_hooks
+            .as_ref()
+            .unwrap_or(&SETTINGS.pre_commit_hooks)
+    }
+
+    fn post_changelog_hooks(&self) -> &Vec<HookSetting> {
+        self.post_changelog_hooks
+            .as_ref()
+            .unwrap_or(&SETTINGS.post_changelog_package_hooks)
+    }
+
+    fn on_bump_failure_hooks(&self) -> &Vec<HookSetting> {
+        self.on_bump_failure_hooks
+            .as_ref()
+            .unwrap_or(&SETTINGS.on_bump_failure_package_hooks)
+    }
 }
@@ -4,12 +4,41 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
+use chrono::{Datelike, Utc};
+use cmd_lib::run_cmd;
 use cocogitto::settings::Settings;
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
+use predicates::prelude::predicate;
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
 use std::path::Path;
 
+#[sealed_test]
+fn auto_bump_dry_run_prints_plan() -> Result<()> {
+    git_init()?;
+    git_add("pre_bump_hooks = [\"echo {{version}}\"]", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout("1.1.0\n")
+        .stderr(predicate::str::contains("1.0.0 -> 1.1.0"))
+        .stderr(predicate::str::contains("Changelog: CHANGELOG.md"))
+        .stderr(predicate::str::contains("+## 1.1.0"))
+        .stderr(predicate::str::contains("[pre-bump] echo 1.1.0 (in .)"))
+        .stderr(predicate::str::contains("Commit: chore(version): 1.1.0"));
+
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 fn auto_bump_from_start_ok() -> Result<()> {
     git_init()?;
@@ -165,171 +194,1205 @@ fn auto_bump_respect_semver_sorting() -> Result<()> {
 }
 
 #[sealed_test]
-fn minor_bump() -> Result<()> {
+fn auto_bump_maintenance_branch_with_reachable_tags_only() -> Result<()> {
+    git_init()?;
+    git_add("reachable_tags_only = true", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git branch release/1.x;)?;
+    git_commit("feat!: breaking feature")?;
+    git_tag("2.0.0")?;
+    run_cmd!(git checkout release/1.x;)?;
+    git_commit("fix: backported fix")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("1.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_prerelease_channel_branch() -> Result<()> {
+    git_init()?;
+    git_add("[branches.next]\nprerelease = \"beta\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git checkout -b next;)?;
+    git_commit("feat!: breaking feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    git_commit("fix: bug fix")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("2.0.0-beta.1")?;
+    assert_tag_exists("2.0.0-beta.2")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_maintenance_branch_out_of_range_fails() -> Result<()> {
+    git_init()?;
+    git_add(
+        "reachable_tags_only = true\n[branches.\"release/*\"]\nrange = \"1.x\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git checkout -b release/1.x;)?;
+    git_commit("fix: backported fix")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    git_commit("feat!: breaking feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Version 2.0.0 is outside of the range `1.x` allowed on branch 'release/1.x'",
+        ));
+
+    assert_tag_exists("1.0.1")?;
+    assert_tag_does_not_exist("2.0.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_without_commit_and_tag() -> Result<()> {
     git_init()?;
+    git_add("post_bump_hooks = [\"touch post-bump-ran\"]", "cog.toml")?;
     git_commit("chore: init")?;
     git_tag("1.0.0")?;
     git_commit("feat: feature")?;
 
     Command::cargo_bin("cog")?
         .arg("bump")
-        .arg("--minor")
+        .arg("--auto")
+        .arg("--no-commit")
+        .arg("--no-tag")
         .assert()
         .success();
 
     assert_that!(Path::new("CHANGELOG.md")).exists();
-    assert_tag_exists("1.1.0")?;
+    assert_that!(Path::new("post-bump-ran")).exists();
+    assert_that!(git_log_head()?).is_equal_to("feat: feature\n".to_string());
+    assert_tag_does_not_exist("1.1.0")?;
     Ok(())
 }
 
 #[sealed_test]
-fn major_bump() -> Result<()> {
+fn auto_bump_without_commit_skips_tag() -> Result<()> {
     git_init()?;
+    git_add("post_bump_hooks = [\"touch post-bump-ran\"]", "cog.toml")?;
     git_commit("chore: init")?;
     git_tag("1.0.0")?;
     git_commit("feat: feature")?;
 
     Command::cargo_bin("cog")?
         .arg("bump")
-        .arg("--major")
+        .arg("--auto")
+        .arg("--no-commit")
         .assert()
         .success();
 
     assert_that!(Path::new("CHANGELOG.md")).exists();
-    assert_tag_exists("2.0.0")?;
+    assert_that!(Path::new("post-bump-ran")).exists();
+    assert_that!(git_log_head()?).is_equal_to("feat: feature\n".to_string());
+    assert_tag_does_not_exist("1.1.0")?;
     Ok(())
 }
 
 #[sealed_test]
-fn patch_bump() -> Result<()> {
+fn auto_bump_with_hook_tables() -> Result<()> {
     git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            post_bump_hooks = [
+                { cmd = "touch major", if = "increment == 'major'" },
+                { cmd = "touch minor", if = "increment == 'minor'" },
+                { cmd = "touch $HOOK_FILE", env = { HOOK_FILE = "from-env" } },
+                { cmd = "touch in-cwd", cwd = "sub" },
+                { cmd = "exit 1", allow_failure = true },
+            ]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    std::fs::create_dir("sub")?;
+    git_add("", "sub/.gitkeep")?;
     git_commit("chore: init")?;
     git_tag("1.0.0")?;
     git_commit("feat: feature")?;
 
     Command::cargo_bin("cog")?
         .arg("bump")
-        .arg("--patch")
+        .arg("--auto")
         .assert()
         .success();
-    assert_that!(Path::new("CHANGELOG.md")).exists();
-    assert_tag_exists("1.0.1")?;
+
+    assert_tag_exists("1.1.0")?;
+    assert_that!(Path::new("major")).does_not_exist();
+    assert_that!(Path::new("minor")).exists();
+    assert_that!(Path::new("from-env")).exists();
+    assert_that!(Path::new("sub/in-cwd")).exists();
     Ok(())
 }
 
 #[sealed_test]
-fn pre_release_bump() -> Result<()> {
+fn auto_bump_exports_hook_environment() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            tag_prefix = "v"
+            post_bump_hooks = [
+                "echo $COG_VERSION $COG_PREVIOUS_VERSION $COG_TAG $COG_INCREMENT > hook-env",
+                "test -f $COG_CHANGELOG && test -z $COG_PACKAGE",
+            ]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("v1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    let hook_env = std::fs::read_to_string("hook-env")?;
+    assert_that!(hook_env.as_str()).is_equal_to("1.1.0 1.0.0 v1.1.0 minor\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_with_hook_shell() -> Result<()> {
     git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            hook_shell = "bash -euo pipefail -c"
+            pre_bump_hooks = ["false | true"]
+            "#
+        ),
+        "cog.toml",
+    )?;
     git_commit("chore: init")?;
     git_tag("1.0.0")?;
     git_commit("feat: feature")?;
 
     Command::cargo_bin("cog")?
         .arg("bump")
-        .arg("--major")
-        .arg("--pre")
-        .arg("alpha")
+        .arg("--auto")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("hook failed with status"));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn failed_bump_shows_hook_output_tail() -> Result<()> {
+    git_init()?;
+    git_add(
+        r#"pre_bump_hooks = ["echo checking && echo 'lint error' >&2 && exit 3"]"#,
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Last lines of the hook output"))
+        .stderr(predicate::str::contains("\n\t  checking"))
+        .stderr(predicate::str::contains("\n\t  lint error"));
+
+    // stdout and stderr lines are captured, their relative order is not guaranteed
+    let log = std::fs::read_to_string(".git/cog/hooks.log")?;
+    assert_that!(log.as_str()).contains("hook failed with status exit status: 3");
+    assert_that!(log.as_str()).contains("    checking\n");
+    assert_that!(log.as_str()).contains("    lint error\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_logs_hook_output() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            hook_log = "logs/hooks.log"
+            pre_bump_hooks = ["echo {{version}}"]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1.1.0"));
+
+    let log = std::fs::read_to_string("logs/hooks.log")?;
+    assert_that!(log.as_str()).contains("`echo 1.1.0`: hook succeeded in");
+    assert_that!(log.as_str()).ends_with("    1.1.0\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_with_empty_hook_log_does_not_capture_output() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            hook_log = ""
+            pre_bump_hooks = ["echo {{version}}"]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
         .assert()
         .success();
 
-    assert_that!(Path::new("CHANGELOG.md")).exists();
-    assert_tag_exists("2.0.0-alpha")?;
+    assert_that!(Path::new(".git/cog/hooks.log")).does_not_exist();
     Ok(())
 }
 
 #[sealed_test]
-#[cfg(target_os = "linux")]
-fn bump_with_hook() -> Result<()> {
-    // Arrange
+fn auto_bump_runs_post_changelog_hooks() -> Result<()> {
     git_init()?;
-    git_add(r#"pre_bump_hooks = ["touch {{version}}"]"#, "cog.toml")?;
+    git_add(
+        indoc!(
+            r#"
+            post_changelog_hooks = ["grep -q '## 1.1.0' $COG_CHANGELOG && echo checked > changelog-checked"]
+            "#
+        ),
+        "cog.toml",
+    )?;
     git_commit("chore: init")?;
     git_tag("1.0.0")?;
     git_commit("feat: feature")?;
 
-    // Act
     Command::cargo_bin("cog")?
         .arg("bump")
-        .arg("--major")
-        // Assert
+        .arg("--auto")
         .assert()
         .success();
 
-    assert_that!(Path::new("2.0.0")).exists();
-    assert_tag_exists("2.0.0")?;
+    // Files created by the hook are part of the version commit
+    let committed = Command::new("git")
+        .args(["show", "--name-only", "--format=", "HEAD"])
+        .output()?;
+    let committed = String::from_utf8(committed.stdout)?;
+    assert_that!(committed.as_str()).contains("changelog-checked");
+    assert_tag_exists("1.1.0")?;
     Ok(())
 }
 
 #[sealed_test]
-#[cfg(target_os = "linux")]
-fn bump_with_profile_hook() -> Result<()> {
-    // Arrange
+fn failed_bump_runs_on_bump_failure_hooks() -> Result<()> {
     git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            pre_bump_hooks = ["exit 1"]
+            on_bump_failure_hooks = ["echo {{version}} > bump-failed"]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
 
-    let config = indoc! {
-        "[bump_profiles.custom]
-            pre_bump_hooks = [ \"echo current {{latest}}\" ]
-            post_bump_hooks = [ \"echo next {{version}}\" ]
-        "
-    };
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .failure();
 
-    git_add(config, "cog.toml")?;
+    let bump_failed = std::fs::read_to_string("bump-failed")?;
+    assert_that!(bump_failed.as_str()).is_equal_to("1.1.0\n");
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_with_invalid_hook_table_fails() -> Result<()> {
+    git_init()?;
+    git_add(
+        r#"pre_bump_hooks = ["echo ok", { cmd = "echo slow", timeout = "soon" }]"#,
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot parse hook at index 1"))
+        .stderr(predicate::str::contains("invalid timeout `soon`"));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
 
+#[sealed_test]
+fn auto_bump_without_changelog() -> Result<()> {
+    git_init()?;
     git_commit("chore: init")?;
     git_tag("1.0.0")?;
     git_commit("feat: feature")?;
 
-    // Act
     Command::cargo_bin("cog")?
         .arg("bump")
-        .arg("--hook-profile")
-        .arg("custom")
-        .arg("--patch")
-        .unwrap()
-        // Assert
+        .arg("--auto")
+        .arg("--no-changelog")
         .assert()
         .success();
 
-    assert_tag_exists("1.0.1")?;
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_latest_tag("1.1.0")?;
     Ok(())
 }
 
 #[sealed_test]
-fn monorepo_dry_run() -> Result<()> {
-    init_monorepo(&mut Settings::default())?;
+fn auto_bump_with_default_increment() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("chore: dependencies update")?;
 
     Command::cargo_bin("cog")?
         .arg("bump")
         .arg("--auto")
-        .arg("--dry-run")
+        .arg("--default-increment")
+        .arg("minor")
         .assert()
-        .success()
-        .stdout(indoc!(
-            "one-0.1.0
-            0.1.0
-            "
-        ));
+        .success();
 
-    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
-    assert_tag_does_not_exist("1.1.0")?;
+    assert_latest_tag("1.1.0")?;
     Ok(())
 }
 
 #[sealed_test]
-fn package_dry_run() -> Result<()> {
-    init_monorepo(&mut Settings::default())?;
+fn auto_bump_skip_without_bump_commits() -> Result<()> {
+    git_init()?;
+    git_add("[bump]\nno_bump_commits = \"skip\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("docs: readme")?;
 
     Command::cargo_bin("cog")?
         .arg("bump")
         .arg("--auto")
-        .arg("--package")
-        .arg("one")
-        .arg("--dry-run")
         .assert()
-        .success()
-        .stdout(indoc!("one-0.1.0\n"));
+        .success();
 
+    assert_latest_tag("1.0.0")?;
     assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
-    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_patch_without_bump_commits() -> Result<()> {
+    git_init()?;
+    git_add("[bump]\nno_bump_commits = \"patch\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("docs: readme")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_latest_tag("1.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_with_build_metadata() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--build")
+        .arg("exp.sha.5114f85")
+        .assert()
+        .success();
+
+    assert_latest_tag("1.1.0+exp.sha.5114f85")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_with_build_metadata_template() -> Result<()> {
+    git_init()?;
+    git_add(
+        "[bump]\nbuild_metadata = \"{{short_sha}}.{{date}}\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    let sha = git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    let date = Utc::now().format("%Y%m%d");
+    assert_latest_tag(&format!("1.1.0+{}.{date}", &sha[0..7]))?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_from_revision_and_previous_version() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat!: legacy breaking change")?;
+    git_tag("release-42")?;
+    git_commit("feat: new feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--from")
+        .arg("release-42")
+        .arg("--previous-version")
+        .arg("1.4.0")
+        .assert()
+        .success();
+
+    assert_tag_exists("1.5.0")?;
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog).contains("new feature");
+    assert_that!(changelog).does_not_contain("legacy breaking change");
+    Ok(())
+}
+
+#[sealed_test]
+fn minor_bump() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--minor")
+        .assert()
+        .success();
+
+    assert_that!(Path::new("CHANGELOG.md")).exists();
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn major_bump() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--major")
+        .assert()
+        .success();
+
+    assert_that!(Path::new("CHANGELOG.md")).exists();
+    assert_tag_exists("2.0.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn patch_bump() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--patch")
+        .assert()
+        .success();
+    assert_that!(Path::new("CHANGELOG.md")).exists();
+    assert_tag_exists("1.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn pre_release_bump() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--major")
+        .arg("--pre")
+        .arg("alpha")
+        .assert()
+        .success();
+
+    assert_that!(Path::new("CHANGELOG.md")).exists();
+    assert_tag_exists("2.0.0-alpha")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn calver_auto_bump_resets_micro_on_new_date() -> Result<()> {
+    git_init()?;
+    git_add(
+        "version_scheme = \"calver\"\ncalver_format = \"YYYY.0M.MICRO\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("2020.01.3")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    let expected = Utc::now().format("%Y.%m.0").to_string();
+    assert_tag_exists(&expected)?;
+    Ok(())
+}
+
+#[sealed_test]
+fn calver_auto_bump_increments_micro_on_same_date() -> Result<()> {
+    git_init()?;
+    git_add("version_scheme = \"calver\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    let today = Utc::now();
+    git_tag(&format!("{}.{}.3", today.year(), today.month()))?;
+    git_commit("fix: bug fix")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists(&format!("{}.{}.4", today.year(), today.month()))?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_hook() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(r#"pre_bump_hooks = ["touch {{version}}"]"#, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--major")
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("2.0.0")).exists();
+    assert_tag_exists("2.0.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_profile_hook() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    let config = indoc! {
+        "[bump_profiles.custom]
+            pre_bump_hooks = [ \"echo current {{latest}}\" ]
+            post_bump_hooks = [ \"echo next {{version}}\" ]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--hook-profile")
+        .arg("custom")
+        .arg("--patch")
+        .unwrap()
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("1.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn monorepo_dry_run() -> Result<()> {
+    init_monorepo(&mut Settings::default())?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(indoc!(
+            "one-0.1.0
+            0.1.0
+            "
+        ));
+
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn package_dry_run() -> Result<()> {
+    init_monorepo(&mut Settings::default())?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--package")
+        .arg("one")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(indoc!("one-0.1.0\n"));
+
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn package_bump_with_custom_tag_format() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            tag_format = "{{package}}/v{{version}}"
+            generate_mono_repository_global_tag = false
+
+            [packages.api]
+            path = "api"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir api)?;
+    git_add("api", "api/file")?;
+    git_commit("feat(api): add api")?;
+    git_tag("api/v0.1.0")?;
+    git_tag("web-api/v5.0.0")?;
+    git_tag("0.9.0-api")?;
+    git_add("fix", "api/file")?;
+    git_commit("fix(api): fix api")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--package")
+        .arg("api")
+        .assert()
+        .success();
+
+    assert_tag_exists("api/v0.1.1")?;
+    let changelog = std::fs::read_to_string("api/CHANGELOG.md")?;
+    assert_that!(changelog).contains("api/v0.1.1");
+    assert_that!(changelog).contains("fix api");
+    assert_that!(changelog).does_not_contain("add api");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_discovered_cargo_packages() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+            packages_discovery = "cargo"
+
+            [packages.cli]
+            changelog_path = "CLI_CHANGELOG.md"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_add("[workspace]\nmembers = [\"crates/*\"]", "Cargo.toml")?;
+    run_cmd!(mkdir -p crates/core crates/cli)?;
+    git_add("[package]\nname = \"core\"", "crates/core/Cargo.toml")?;
+    git_add("[package]\nname = \"cli\"", "crates/cli/Cargo.toml")?;
+    git_commit("feat: add core and cli crates")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("core-0.1.0")?;
+    assert_tag_exists("cli-0.1.0")?;
+    assert_that!(Path::new("crates/core/CHANGELOG.md")).exists();
+    assert_that!(Path::new("CLI_CHANGELOG.md")).exists();
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_discovered_glob_packages() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+            packages_discovery = { glob = "services/*" }
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir -p services/api services/worker)?;
+    git_add("api", "services/api/file")?;
+    git_commit("feat: add api service")?;
+    git_add("worker", "services/worker/file")?;
+    git_commit("fix: fix worker service")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("api-0.1.0")?;
+    assert_tag_exists("worker-0.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_discovered_glob_packages_skips_git_ignored_dirs() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+            packages_discovery = { glob = "**/svc-*" }
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_add("dist/", ".gitignore")?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir -p apps/svc-api dist/svc-api)?;
+    git_add("api", "apps/svc-api/file")?;
+    git_commit("feat: add api service")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("svc-api-0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_fails_on_invalid_package_pattern() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            [packages.api]
+            path = "api"
+            include = ["proto/[*"]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid pattern `proto/[*` in package api",
+        ));
+
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_package_with_include_and_exclude_patterns() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+
+            [packages.api]
+            path = "api"
+            include = ["proto/**"]
+            exclude = ["**/*.md"]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir api proto)?;
+    git_add("api", "api/file")?;
+    git_commit("feat: add api")?;
+    git_tag("api-0.1.0")?;
+    git_add("docs", "api/README.md")?;
+    git_commit("feat: document the api")?;
+    git_add("message Api {}", "proto/api.proto")?;
+    git_commit("fix: fix api proto")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("api-0.1.1")?;
+    assert_tag_does_not_exist("api-0.2.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_fixed_monorepo_versioning() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            [monorepo]
+            versioning = "fixed"
+
+            [packages.one]
+            path = "one"
+
+            [packages.two]
+            path = "two"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one two)?;
+    git_add("one", "one/file")?;
+    git_commit("feat: package one feature")?;
+    git_tag("0.1.0")?;
+    git_add("fix", "one/fix")?;
+    git_commit("fix: package one fix")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.1")?;
+    assert_tag_exists("one-0.1.1")?;
+    assert_tag_exists("two-0.1.1")?;
+    assert_that!(Path::new("one/CHANGELOG.md")).exists();
+    assert_that!(Path::new("two/CHANGELOG.md")).exists();
+    Ok(())
+}
+
+#[sealed_test]
+fn monorepo_bump_runs_post_changelog_hooks_once_per_changelog() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            post_changelog_hooks = ["echo global >> $(git rev-parse --show-toplevel)/runs"]
+            post_changelog_package_hooks = ["echo $COG_PACKAGE >> $(git rev-parse --show-toplevel)/runs"]
+
+            [packages.one]
+            path = "one"
+
+            [packages.two]
+            path = "two"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one two)?;
+    git_add("one", "one/file")?;
+    git_commit("feat: package one feature")?;
+    git_add("two", "two/file")?;
+    git_commit("feat: package two feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    let runs = std::fs::read_to_string("runs")?;
+    let mut runs: Vec<&str> = runs.lines().collect();
+    runs.sort_unstable();
+    assert_that!(runs).is_equal_to(vec!["global", "one", "two"]);
+    Ok(())
+}
+
+#[sealed_test]
+fn package_bump_renders_hook_context_variables() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+
+            [packages.one]
+            path = "one"
+            pre_bump_hooks = [
+                "echo {{date:%Y}} {{commit_sha}} {{short_sha}} {{branch}} {{package_path}} > ../vars",
+            ]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one)?;
+    git_add("one", "one/file")?;
+    git_commit("feat(one): package one feature")?;
+    let head = Command::new("git").args(["rev-parse", "HEAD"]).output()?;
+    let head = String::from_utf8(head.stdout)?.trim().to_string();
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    let vars = std::fs::read_to_string("vars")?;
+    let vars: Vec<&str> = vars.split_whitespace().collect();
+    assert_that!(vars).has_length(5);
+    assert_that!(vars[0]).is_equal_to(Utc::now().year().to_string().as_str());
+    assert_that!(vars[1]).is_equal_to(head.as_str());
+    assert_that!(vars[2]).is_equal_to(&head[..7]);
+    assert_that!(vars[3]).is_equal_to("master");
+    assert_that!(Path::new(vars[4]).ends_with("one")).is_true();
+    Ok(())
+}
+
+#[sealed_test]
+fn package_commit_types_can_disable_default_bumps() -> Result<()> {
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+
+            [packages.docs-site]
+            path = "docs-site"
+
+            [packages.docs-site.commit_types]
+            feat = { changelog_title = "Features", bump = "none" }
+            docs = { changelog_title = "Content", bump = "patch" }
+
+            [packages.lib]
+            path = "lib"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("docs-site-0.1.0")?;
+    git_tag("lib-0.1.0")?;
+    run_cmd!(mkdir docs-site lib)?;
+    git_add("docs", "docs-site/file")?;
+    git_add("lib", "lib/file")?;
+    git_commit("feat: add search")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("lib-0.2.0")?;
+    assert_tag_does_not_exist("docs-site-0.2.0")?;
+    assert_tag_does_not_exist("docs-site-0.1.1")?;
+
+    git_add("guide", "docs-site/guide")?;
+    git_commit("docs: write guide")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Found docs commit"));
+
+    assert_tag_exists("docs-site-0.1.1")?;
+    Ok(())
+}
+
+// Package only monorepo where package one has a non bump commit since `one-0.1.0`
+fn init_package_only_monorepo_without_bump_commits(no_bump_commits: &str) -> Result<()> {
+    git_init()?;
+    git_add(
+        &formatdoc!(
+            r#"
+            generate_mono_repository_global_tag = false
+
+            [bump]
+            no_bump_commits = "{no_bump_commits}"
+
+            [packages.one]
+            path = "one"
+
+            [packages.two]
+            path = "two"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one two)?;
+    git_add("one", "one/file")?;
+    git_add("two", "two/file")?;
+    git_commit("feat: packages features")?;
+    git_tag("one-0.1.0")?;
+    git_tag("two-0.1.0")?;
+    git_add("docs", "one/README.md")?;
+    git_commit("docs: package one readme")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn package_auto_bump_skip_without_bump_commits() -> Result<()> {
+    init_package_only_monorepo_without_bump_commits("skip")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_that!(git_log_head()?).is_equal_to("docs: package one readme\n".to_string());
+    assert_tag_does_not_exist("one-0.1.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn package_auto_bump_error_without_bump_commits() -> Result<()> {
+    init_package_only_monorepo_without_bump_commits("error")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .failure();
+
+    assert_that!(git_log_head()?).is_equal_to("docs: package one readme\n".to_string());
+    assert_tag_does_not_exist("one-0.1.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn package_auto_bump_patch_without_bump_commits() -> Result<()> {
+    init_package_only_monorepo_without_bump_commits("patch")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("one-0.1.1")?;
+    assert_tag_does_not_exist("two-0.1.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn package_auto_bump_with_default_increment() -> Result<()> {
+    init_package_only_monorepo_without_bump_commits("error")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--default-increment")
+        .arg("minor")
+        .assert()
+        .success();
+
+    assert_tag_exists("one-0.2.0")?;
+    assert_tag_does_not_exist("two-0.2.0")?;
     Ok(())
 }
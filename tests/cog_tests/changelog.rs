@@ -437,3 +437,78 @@ fn get_changelog_whith_custom_template() -> Result<()> {
     );
     Ok(())
 }
+
+#[sealed_test]
+fn get_package_changelog_at_tag() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[packages.one]\npath = \"one\"\n[packages.two]\npath = \"two\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one two)?;
+    git_add("one", "one/file")?;
+    let commit_one = git_commit("feat: package one feature")?;
+    git_add("two", "two/file")?;
+    git_commit("feat: package two feature")?;
+    git_tag("one-0.1.0")?;
+    git_add("fix", "one/fix")?;
+    git_commit("fix: package one fix")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--package")
+        .arg("one")
+        .arg("--at")
+        .arg("one-0.1.0")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = changelog.get_output();
+    let changelog = String::from_utf8_lossy(&changelog.stdout);
+
+    assert!(changelog.starts_with("## one-0.1.0 - "));
+    assert!(changelog.contains(&format!(
+        "- package one feature - ({}) - Tom",
+        &commit_one[0..7]
+    )));
+    assert!(!changelog.contains("package two feature"));
+    assert!(!changelog.contains("package one fix"));
+    Ok(())
+}
+
+#[sealed_test]
+fn get_package_changelog_from_legacy_tag() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "tag_prefix = \"v\"\nlegacy_tag_prefixes = [\"\"]\n[packages.one]\npath = \"one\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one)?;
+    git_add("one", "one/file")?;
+    git_commit("fix: package one fix")?;
+    git_tag("one-1.0.0")?;
+    git_add("feature", "one/feature")?;
+    git_commit("feat: package one feature")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--package")
+        .arg("one")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = changelog.get_output();
+    let changelog = String::from_utf8_lossy(&changelog.stdout);
+
+    assert!(changelog.contains("package one feature"));
+    assert!(!changelog.contains("package one fix"));
+    Ok(())
+}
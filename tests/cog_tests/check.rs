@@ -83,3 +83,31 @@ fn cog_check_from_latest_tag_failure() -> Result<()> {
         .stderr(predicate::str::contains("Found 1 non compliant commits"));
     Ok(())
 }
+
+#[sealed_test]
+fn cog_check_package_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[packages.one]\npath = \"one\"\n[packages.two]\npath = \"two\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    std::fs::create_dir_all("one")?;
+    std::fs::create_dir_all("two")?;
+    git_add("one", "one/file")?;
+    git_commit("feat: package one feature")?;
+    git_add("two", "two/file")?;
+    git_commit("toto: package two feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg("--package")
+        .arg("one")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No errored commits"));
+    Ok(())
+}
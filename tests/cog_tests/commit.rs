@@ -27,6 +27,68 @@ fn commit_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn commit_runs_pre_commit_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            pre_commit_hooks = ["git add generated"]
+
+            [packages.cli]
+            path = "cli"
+            pre_commit_hooks = ["test $COG_PACKAGE = cli", "touch ../generated"]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    std::fs::create_dir("cli")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("generate a file")
+        .arg("cli")
+        .assert()
+        .success();
+
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("commit the generated file")
+        .assert()
+        .success();
+
+    // Assert
+    let committed = Command::new("git")
+        .args(["show", "--name-only", "--format=", "HEAD"])
+        .output()?;
+    assert_eq!(String::from_utf8(committed.stdout)?, "generated\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn failing_pre_commit_hook_aborts_commit() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(r#"pre_commit_hooks = ["exit 1"]"#, "cog.toml")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        // Assert
+        .assert()
+        .failure();
+
+    let log = Command::new("git").args(["log"]).output()?;
+    assert!(!log.status.success());
+    Ok(())
+}
+
 #[sealed_test]
 fn commit_fail_if_not_a_repository() -> Result<()> {
     // Act
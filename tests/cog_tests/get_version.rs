@@ -5,7 +5,7 @@ use assert_cmd::prelude::*;
 use predicates::prelude::predicate;
 use sealed_test::prelude::*;
 
-use cocogitto::settings::Settings;
+use cocogitto::settings::{MonoRepoVersioning, NoBumpCommits, Settings};
 
 use crate::helpers::*;
 
@@ -146,6 +146,26 @@ fn get_version_after_bump_fallback_not_used_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn get_calver_version_ok() -> Result<()> {
+    git_init()?;
+    git_add(
+        "version_scheme = \"calver\"\ncalver_format = \"YY.0M.MICRO\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("23.02.1")?;
+    git_tag("1.10.0")?;
+
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        .assert()
+        .success()
+        .stdout(predicate::eq(b"23.02.1\n" as &[u8]));
+
+    Ok(())
+}
+
 #[sealed_test]
 fn get_initial_version_of_monorepo_expected_error() -> Result<()> {
     init_monorepo(&mut Settings::default())?;
@@ -229,3 +249,107 @@ fn get_version_of_monorepo_package_having_own_version_ok() -> Result<()> {
 
     Ok(())
 }
+
+#[sealed_test]
+fn get_next_version_on_dirty_tree_ok() -> Result<()> {
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    git_commit("fix: bug fix")?;
+    std::fs::write("dirty", "uncommitted changes")?;
+
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        .arg("--next")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("1.1.0"))
+        .stderr(predicate::str::contains("minor bump from 1.0.0"));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn get_next_package_version_json_ok() -> Result<()> {
+    init_monorepo(&mut Settings::default())?;
+    git_tag("one-0.1.0")?;
+    git_add("fix", "one/file")?;
+    git_commit("fix(one): package fix")?;
+
+    let output = Command::cargo_bin("cog")?
+        .arg("get-version")
+        .arg("--next")
+        .arg("--package")
+        .arg("one")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(output["current"], "0.1.0");
+    assert_eq!(output["next"], "0.1.1");
+    assert_eq!(output["increment"], "patch");
+    assert_eq!(output["commits"][0]["summary"], "package fix");
+    Ok(())
+}
+
+#[sealed_test]
+fn get_next_monorepo_version_applies_no_bump_commits_policy() -> Result<()> {
+    let mut settings = Settings::default();
+    settings.bump.no_bump_commits = NoBumpCommits::Patch;
+    init_monorepo(&mut settings)?;
+    git_tag("0.1.0")?;
+    git_tag("one-0.1.0")?;
+    git_add("tidy", "one/file")?;
+    git_commit("chore(one): tidy")?;
+
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        .arg("--next")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("0.1.1"));
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn get_next_package_version_with_fixed_versioning() -> Result<()> {
+    let mut settings = Settings::default();
+    settings.monorepo.versioning = MonoRepoVersioning::Fixed;
+    init_monorepo(&mut settings)?;
+    git_tag("0.1.0")?;
+    git_tag("one-0.1.0")?;
+    git_commit("feat: feature outside packages")?;
+
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        .arg("--next")
+        .arg("--package")
+        .arg("one")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("0.2.0"));
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("one-0.2.0")?;
+    Ok(())
+}
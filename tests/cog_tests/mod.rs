@@ -4,4 +4,6 @@ mod check;
 mod commit;
 mod get_version;
 mod init;
+mod packages;
+mod tags;
 mod verify;
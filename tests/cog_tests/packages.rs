@@ -0,0 +1,72 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use cmd_lib::run_cmd;
+use indoc::indoc;
+use predicates::prelude::{predicate, PredicateBooleanExt};
+use sealed_test::prelude::*;
+
+use crate::helpers::*;
+
+const MONOREPO_SETTINGS: &str = indoc!(
+    r#"
+    [packages.one]
+    path = "one"
+
+    [packages.two]
+    path = "two"
+    "#
+);
+
+#[sealed_test]
+fn list_changed_packages() -> Result<()> {
+    git_init()?;
+    git_add(MONOREPO_SETTINGS, "cog.toml")?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one two)?;
+    git_add("one", "one/file")?;
+    git_add("two", "two/file")?;
+    git_commit("feat: add packages")?;
+    git_tag("one-0.1.0")?;
+    git_tag("two-0.1.0")?;
+    git_add("fix", "one/fix")?;
+    git_commit("fix: fix package one")?;
+
+    Command::cargo_bin("cog")?
+        .arg("packages")
+        .arg("changed")
+        .assert()
+        .success()
+        .stdout("one\n");
+
+    Ok(())
+}
+
+#[sealed_test]
+fn list_changed_packages_since_revision_as_json() -> Result<()> {
+    git_init()?;
+    git_add(MONOREPO_SETTINGS, "cog.toml")?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir one two)?;
+    git_add("one", "one/file")?;
+    let since = git_commit("feat: add package one")?;
+    git_add("two", "two/file")?;
+    git_commit("feat: add package two")?;
+
+    Command::cargo_bin("cog")?
+        .arg("packages")
+        .arg("changed")
+        .arg("--since")
+        .arg(since)
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""package": "two""#))
+        .stdout(predicate::str::contains(r#""next": "0.1.0""#))
+        .stdout(predicate::str::contains(r#""increment": "minor""#))
+        .stdout(predicate::str::contains(r#""package": "one""#).not());
+
+    Ok(())
+}
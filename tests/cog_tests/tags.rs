@@ -0,0 +1,65 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use sealed_test::prelude::*;
+
+use crate::helpers::*;
+
+#[sealed_test]
+fn migrate_legacy_tags() -> Result<()> {
+    git_init()?;
+    git_add(
+        "tag_prefix = \"v\"\nlegacy_tag_prefixes = [\"\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    git_tag("1.1.0")?;
+
+    Command::cargo_bin("cog")?
+        .arg("tags")
+        .arg("migrate")
+        .assert()
+        .success();
+
+    assert_tag_exists("v1.0.0")?;
+    assert_tag_exists("v1.1.0")?;
+
+    git_commit("feat: another feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("v1.2.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_from_legacy_tag() -> Result<()> {
+    git_init()?;
+    git_add(
+        "tag_prefix = \"v\"\nlegacy_tag_prefixes = [\"\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("fix: bug fix")?;
+    git_tag("1.2.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("v1.3.0")?;
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    assert!(changelog.contains("feature"));
+    assert!(!changelog.contains("bug fix"));
+    Ok(())
+}
@@ -73,9 +73,9 @@ pub fn git_status() -> Result<String> {
 }
 
 /// Write the given content to the provided path and add it to the git index
-pub fn git_add<S: AsRef<Path>>(content: &str, path: S) -> Result<()>
+pub fn git_add<S>(content: &str, path: S) -> Result<()>
 where
-    S: ToString,
+    S: AsRef<Path> + ToString,
 {
     let path = path.to_string();
     run_cmd!(
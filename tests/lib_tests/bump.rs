@@ -1,5 +1,7 @@
 use anyhow::Result;
 use cmd_lib::run_cmd;
+use cocogitto::command::bump::BumpOptions;
+use cocogitto::conventional::commit::{CommitBump, CommitConfig};
 use cocogitto::settings::{MonoRepoPackage, Settings};
 use cocogitto::{conventional::version::IncrementCommand, CocoGitto};
 use sealed_test::prelude::*;
@@ -21,7 +23,14 @@ fn bump_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -47,6 +56,7 @@ fn annotated_bump_ok() -> Result<()> {
         None,
         Some(String::from("Release version {{version}}")),
         false,
+        BumpOptions::default(),
     );
 
     // Assert
@@ -68,7 +78,14 @@ fn monorepo_bump_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_monorepo_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_monorepo_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -92,8 +109,14 @@ fn monorepo_bump_manual_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result =
-        cocogitto.create_monorepo_version(IncrementCommand::Major, None, None, None, false);
+    let result = cocogitto.create_monorepo_version(
+        IncrementCommand::Major,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -114,7 +137,14 @@ fn monorepo_with_tag_prefix_bump_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_monorepo_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_monorepo_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -142,6 +172,7 @@ fn package_bump_ok() -> Result<()> {
         None,
         None,
         false,
+        BumpOptions::default(),
     );
 
     // Assert
@@ -151,6 +182,60 @@ fn package_bump_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn package_bump_with_package_changelog_template() -> Result<()> {
+    // Arrange
+    let mut packages = HashMap::new();
+    let one = || MonoRepoPackage {
+        path: PathBuf::from("one"),
+        public_api: false,
+        template: Some("remote".to_owned()),
+        remote: Some("github.com".to_owned()),
+        owner: Some("acme".to_owned()),
+        repository: Some("one".to_owned()),
+        ..Default::default()
+    };
+
+    packages.insert("one".to_owned(), one());
+
+    let settings = Settings {
+        packages,
+        ..Default::default()
+    };
+
+    let settings = toml::to_string(&settings)?;
+
+    git_init()?;
+    run_cmd!(
+        echo $settings > cog.toml;
+        git add .;
+        git commit -m "chore: first commit";
+        mkdir one;
+        echo "one" > one/file;
+        git add .;
+        git commit -m "feat: add package one";
+    )?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    cocogitto.create_package_version(
+        ("one", &one()),
+        IncrementCommand::AutoPackage("one".to_owned()),
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    )?;
+
+    // Assert
+    assert_tag_exists("one-0.1.0")?;
+    let changelog = std::fs::read_to_string("one/CHANGELOG.md")?;
+    assert_that!(changelog).contains("https://github.com/acme/one/commit/");
+    Ok(())
+}
+
 #[sealed_test]
 fn consecutive_package_bump_ok() -> Result<()> {
     // Arrange
@@ -209,6 +294,7 @@ fn consecutive_package_bump_ok() -> Result<()> {
         None,
         None,
         false,
+        BumpOptions::default(),
     )?;
 
     cocogitto.create_package_version(
@@ -218,6 +304,7 @@ fn consecutive_package_bump_ok() -> Result<()> {
         None,
         None,
         false,
+        BumpOptions::default(),
     )?;
 
     run_cmd!(
@@ -233,6 +320,7 @@ fn consecutive_package_bump_ok() -> Result<()> {
         None,
         None,
         false,
+        BumpOptions::default(),
     )?;
 
     // Assert
@@ -254,7 +342,14 @@ fn should_fallback_to_0_0_0_when_there_is_no_tag() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -313,7 +408,7 @@ fn auto_bump_package_only_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    cocogitto.create_all_package_version_auto(None, None, false)?;
+    cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default())?;
 
     assert_tag_exists("jenkins-0.1.0")?;
     assert_tag_exists("thumbor-0.1.0")?;
@@ -325,13 +420,199 @@ fn auto_bump_package_only_ok() -> Result<()> {
         git commit -m "fix(jenkins): bug fix on jenkins package";
     )?;
 
-    cocogitto.create_all_package_version_auto(None, None, false)?;
+    cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default())?;
 
     // Assert
     assert_tag_exists("jenkins-0.1.1")?;
     Ok(())
 }
 
+#[sealed_test]
+fn auto_bump_package_cascades_to_dependants() -> Result<()> {
+    // Arrange
+    let mut packages = HashMap::new();
+    packages.insert(
+        "core".to_owned(),
+        MonoRepoPackage {
+            path: PathBuf::from("core"),
+            public_api: false,
+            ..Default::default()
+        },
+    );
+    packages.insert(
+        "cli".to_owned(),
+        MonoRepoPackage {
+            path: PathBuf::from("cli"),
+            public_api: false,
+            depends_on: vec!["core".to_owned()],
+            ..Default::default()
+        },
+    );
+
+    let settings = Settings {
+        packages,
+        generate_mono_repository_global_tag: false,
+        ..Default::default()
+    };
+
+    let settings = toml::to_string(&settings)?;
+
+    git_init()?;
+    run_cmd!(
+        echo $settings > cog.toml;
+        git add .;
+        git commit -m "chore: first commit";
+        mkdir core cli;
+        echo "core" > core/file;
+        echo "cli" > cli/file;
+        git add .;
+        git commit -m "feat: add core and cli";
+    )?;
+
+    let mut cocogitto = CocoGitto::get()?;
+    cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default())?;
+
+    run_cmd!(
+        echo "fix" > core/fix;
+        git add .;
+        git commit -m "fix(core): core bug fix";
+    )?;
+
+    // Act
+    cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default())?;
+
+    // Assert
+    assert_tag_exists("core-0.1.1")?;
+    assert_tag_exists("cli-0.1.1")?;
+    let changelog = std::fs::read_to_string("cli/CHANGELOG.md")?;
+    assert_that!(changelog).contains("- dependency core bumped");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_package_with_package_commit_types() -> Result<()> {
+    // Arrange
+    let mut commit_types = HashMap::new();
+    commit_types.insert(
+        "docs".to_owned(),
+        CommitConfig {
+            changelog_title: "Content".to_owned(),
+            bump: Some(CommitBump::Patch),
+        },
+    );
+
+    let mut packages = HashMap::new();
+    packages.insert(
+        "docs-site".to_owned(),
+        MonoRepoPackage {
+            path: PathBuf::from("docs-site"),
+            public_api: false,
+            commit_types,
+            ..Default::default()
+        },
+    );
+    packages.insert(
+        "lib".to_owned(),
+        MonoRepoPackage {
+            path: PathBuf::from("lib"),
+            public_api: false,
+            ..Default::default()
+        },
+    );
+
+    let settings = Settings {
+        packages,
+        generate_mono_repository_global_tag: false,
+        ..Default::default()
+    };
+
+    let settings = toml::to_string(&settings)?;
+
+    git_init()?;
+    run_cmd!(
+        echo $settings > cog.toml;
+        git add .;
+        git commit -m "chore: first commit";
+        mkdir docs-site lib;
+        echo "docs" > docs-site/file;
+        echo "lib" > lib/file;
+        git add .;
+        git commit -m "feat: add docs-site and lib";
+    )?;
+
+    let mut cocogitto = CocoGitto::get()?;
+    cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default())?;
+
+    run_cmd!(
+        echo "docs" > docs-site/guide;
+        echo "docs" > lib/README;
+        git add .;
+        git commit -m "docs: write guides";
+    )?;
+
+    // Act
+    cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default())?;
+
+    // Assert
+    assert_tag_exists("docs-site-0.1.1")?;
+    assert_tag_does_not_exist("lib-0.1.1")?;
+    let changelog = std::fs::read_to_string("docs-site/CHANGELOG.md")?;
+    assert_that!(changelog).contains("#### Content");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_package_with_circular_dependencies_fails() -> Result<()> {
+    // Arrange
+    let mut packages = HashMap::new();
+    packages.insert(
+        "core".to_owned(),
+        MonoRepoPackage {
+            path: PathBuf::from("core"),
+            depends_on: vec!["cli".to_owned()],
+            ..Default::default()
+        },
+    );
+    packages.insert(
+        "cli".to_owned(),
+        MonoRepoPackage {
+            path: PathBuf::from("cli"),
+            depends_on: vec!["core".to_owned()],
+            ..Default::default()
+        },
+    );
+
+    let settings = Settings {
+        packages,
+        generate_mono_repository_global_tag: false,
+        ..Default::default()
+    };
+
+    let settings = toml::to_string(&settings)?;
+
+    git_init()?;
+    run_cmd!(
+        echo $settings > cog.toml;
+        git add .;
+        git commit -m "chore: first commit";
+        mkdir core;
+        echo "core" > core/file;
+        git add .;
+        git commit -m "feat(core): add core";
+    )?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result =
+        cocogitto.create_all_package_version_auto(None, None, false, BumpOptions::default());
+
+    // Assert
+    assert_that!(result.unwrap_err().to_string())
+        .is_equal_to("Circular dependency between packages: cli -> core -> cli".to_string());
+    Ok(())
+}
+
 // FIXME: Failing on non compliant tag should be configurable
 //  until it's implemented we will ignore non compliant tags
 // #[sealed_test]
@@ -377,7 +658,14 @@ fn bump_with_whitelisted_branch_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -402,7 +690,14 @@ fn bump_with_whitelisted_branch_fails() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result.unwrap_err().to_string()).is_equal_to(
@@ -431,7 +726,14 @@ fn bump_with_whitelisted_branch_pattern_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_ok();
@@ -456,7 +758,14 @@ fn bump_with_whitelisted_branch_pattern_err() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(IncrementCommand::Auto, None, None, None, false);
+    let result = cocogitto.create_version(
+        IncrementCommand::Auto,
+        None,
+        None,
+        None,
+        false,
+        BumpOptions::default(),
+    );
 
     // Assert
     assert_that!(result).is_err();
@@ -18,7 +18,7 @@ fn get_unfiltered_logs() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let logs = cocogitto.get_log(filters)?;
+    let logs = cocogitto.get_log(filters, None)?;
 
     // Assert
     assert_that!(logs).contains("I am afraid I can't do that Dave");
@@ -39,7 +39,7 @@ fn get_log_with_no_errors() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let logs = cocogitto.get_log(filters)?;
+    let logs = cocogitto.get_log(filters, None)?;
 
     // Assert
     assert_that!(logs).does_not_contain("Errored commit:");
@@ -48,3 +48,27 @@ fn get_log_with_no_errors() -> Result<()> {
 
     Ok(())
 }
+
+#[sealed_test]
+fn get_package_logs() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[packages.one]\npath = \"one\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    std::fs::create_dir_all("one")?;
+    git_add("one", "one/file")?;
+    git_commit("feat: package one feature")?;
+    git_commit("feat: repository feature")?;
+
+    let filters = CommitFilters(Vec::with_capacity(0));
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let logs = cocogitto.get_log(filters, Some("one"))?;
+
+    // Assert
+    assert_that!(logs).contains("package one feature");
+    assert_that!(logs).does_not_contain("repository feature");
+
+    Ok(())
+}